@@ -3,12 +3,16 @@
 use srml_support::{StorageValue, StorageMap, dispatch::Result, decl_module, decl_event, decl_storage, ensure};
 use srml_support::traits::{Currency};
 use system::{self, ensure_signed};
-use runtime_primitives::traits::{As, Zero};
+use runtime_primitives::traits::{As, Zero, CheckedMul};
 use rstd::prelude::*;
+use rstd::marker::PhantomData;
 
 pub use super::election::{self, Seats, Seat, CouncilElected};
 pub use super::{ GovernanceCurrency, BalanceOf };
 
+const DEFAULT_MIN_ELIGIBLE_PROPOSALS: u32 = 5;
+const DEFAULT_REWARD_PAYOUT_INTERVAL: u64 = 600;
+
 // Hook For announcing that council term has ended
 pub trait CouncilTermEnded {
     fn council_term_ended();
@@ -24,30 +28,152 @@ impl<X: CouncilTermEnded> CouncilTermEnded for (X,) {
     }
 }
 
+/// Origin of a call that has been approved by councilors.
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum RawOrigin {
+    /// Params:
+    /// * Number of councilors that approved the call.
+    /// * Size of the council at the moment of approval.
+    Members(u32, u32),
+}
+
+/// Origin for the council module.
+pub type Origin = RawOrigin;
+
+// Check for privileged calls that can be made not only by root
+pub trait EnsureCouncilOrigin<O> {
+    fn ensure_council_origin(o: O) -> Result;
+}
+
+/// Allows root and calls approved by more than a half of the council.
+pub struct RootOrCouncilMajority<T>(PhantomData<T>);
+
+impl<T: system::Trait> EnsureCouncilOrigin<T::Origin> for RootOrCouncilMajority<T>
+    where T::Origin: Clone + Into<Option<RawOrigin>>
+{
+    fn ensure_council_origin(o: T::Origin) -> Result {
+        if system::ensure_root(o.clone()).is_ok() {
+            return Ok(());
+        }
+        match o.into() {
+            Some(RawOrigin::Members(approvals, council_size)) if approvals * 2 > council_size => Ok(()),
+            _ => Err("bad origin: expected root or approval of council majority"),
+        }
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+/// Participation of a councilor in voting on proposals finalized during the current term.
+pub struct Participation {
+    /// Number of finalized proposals the councilor could vote on.
+    pub eligible: u32,
+    /// Number of those proposals the councilor voted on.
+    pub voted: u32,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+/// What happens to a councilor whose participation is below the min participation.
+pub enum InactivityPenalty<Balance> {
+    /// Only an event is emitted.
+    NoPenalty,
+    /// Slash up to a given amount from the seat stake of a councilor.
+    /// Stakes of backers of the councilor are not touched.
+    Slash(Balance),
+    /// Remove a councilor from the council. Seat and backing stakes are not slashed,
+    /// they are returned through the next election like stakes of the rest of the council.
+    Removal,
+}
+
+impl<Balance> Default for InactivityPenalty<Balance> {
+    fn default() -> Self {
+        InactivityPenalty::NoPenalty
+    }
+}
+
 pub trait Trait: system::Trait + GovernanceCurrency {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
     type CouncilTermEnded: CouncilTermEnded;
+
+    /// Origin that is allowed to make privileged calls of governance modules.
+    type CouncilOrigin: EnsureCouncilOrigin<Self::Origin>;
 }
 
 decl_storage! {
     trait Store for Module<T: Trait> as Council {
         ActiveCouncil get(active_council) config(): Seats<T::AccountId, BalanceOf<T>>;
         TermEndsAt get(term_ends_at) config() : T::BlockNumber = T::BlockNumber::sa(1);
+
+        ParticipationOf get(participation_of): map T::AccountId => Participation;
+
+        /// A percent (up to 100) of eligible proposals that a councilor should vote on.
+        /// Zero disables inactivity checks.
+        MinParticipationPercent get(min_participation_percent) config(): u32 = 0;
+
+        /// Participation of a councilor is checked only after this number of eligible proposals.
+        MinEligibleProposals get(min_eligible_proposals) config(): u32 = DEFAULT_MIN_ELIGIBLE_PROPOSALS;
+
+        InactivityPenaltyOf get(inactivity_penalty) config(): InactivityPenalty<BalanceOf<T>>;
+
+        /// Seats of councilors removed during the current term. Their seat and backing stakes
+        /// stay reserved until the next election takes them over as transferable stakes.
+        RemovedSeats get(removed_seats): Seats<T::AccountId, BalanceOf<T>>;
+
+        // Rewards:
+
+        /// Amount that every councilor earns per block.
+        RewardPerBlock get(reward_per_block) config(): BalanceOf<T>;
+
+        /// Amount per block that is split among councilors in proportion to total stakes of their seats.
+        StakeBonusPerBlock get(stake_bonus_per_block) config(): BalanceOf<T>;
+
+        /// Number of blocks between reward payouts.
+        RewardPayoutInterval get(reward_payout_interval) config(): T::BlockNumber =
+            T::BlockNumber::sa(DEFAULT_REWARD_PAYOUT_INTERVAL);
+
+        /// Account that rewards are paid from. If not set, rewards are minted.
+        RewardTreasury get(reward_treasury) config(): Option<T::AccountId>;
+
+        /// Rewards have been paid for blocks up to this one.
+        LastRewardPayoutAt get(last_reward_payout_at): T::BlockNumber;
+
+        NextRewardPayoutAt get(next_reward_payout_at): T::BlockNumber;
     }
 }
 
 /// Event for this module.
 decl_event!(
-    pub enum Event<T> where <T as system::Trait>::BlockNumber {
+    pub enum Event<T> where <T as system::Trait>::BlockNumber, <T as system::Trait>::AccountId, Balance = BalanceOf<T> {
         CouncilTermEnded(BlockNumber),
         NewCouncilTermStarted(BlockNumber),
+
+        /// Params:
+        /// * Councilor.
+        /// * Number of proposals the councilor voted on.
+        /// * Number of proposals the councilor could vote on.
+        CouncilorInactive(AccountId, u32, u32),
+
+        CouncilorSlashedForInactivity(AccountId, Balance),
+        CouncilorRemovedForInactivity(AccountId),
+
+        CouncilorRewarded(AccountId, Balance),
+
+        /// A reward could not be paid, e.g. because the reward treasury is too low.
+        CouncilorRewardNotPaid(AccountId, Balance),
     }
 );
 
 impl<T: Trait> CouncilElected<Seats<T::AccountId, BalanceOf<T>>, T::BlockNumber> for Module<T> {
     fn council_elected(seats: Seats<T::AccountId, BalanceOf<T>>, term: T::BlockNumber) {
+        // Outgoing councilors are paid for the blocks they have served.
+        Self::pay_council_rewards(<system::Module<T>>::block_number());
+        Self::reset_participation();
         <ActiveCouncil<T>>::put(seats);
+        // Stakes of removed councilors have been released by the election.
+        <RemovedSeats<T>>::kill();
 
         let next_term_ends_at = <system::Module<T>>::block_number() + term;
         <TermEndsAt<T>>::put(next_term_ends_at);
@@ -64,6 +190,145 @@ impl<T: Trait> Module<T> {
     pub fn is_councilor(sender: &T::AccountId) -> bool {
         Self::active_council().iter().any(|c| c.member == *sender)
     }
+
+    /// Adds a zero staked council member.
+    pub fn try_add_council_member(account: T::AccountId) -> Result {
+        ensure!(!Self::is_councilor(&account), "cannot add same account multiple times");
+        // Rewards earned so far are split among the current councilors only.
+        Self::pay_council_rewards(<system::Module<T>>::block_number());
+        let seat = Seat {
+            member: account,
+            stake: BalanceOf::<T>::zero(),
+            backers: vec![]
+        };
+
+        // add member to existing council
+        <ActiveCouncil<T>>::mutate(|council| council.push(seat));
+        Ok(())
+    }
+
+    /// Removes a member from the active council. Its seat is kept in removed seats,
+    /// so the next election can return seat and backing stakes to their owners.
+    pub fn try_remove_council_member(account_to_remove: T::AccountId) -> Result {
+        ensure!(Self::is_councilor(&account_to_remove), "account is not a councilor");
+        // The removed councilor is paid for the blocks it has served.
+        Self::pay_council_rewards(<system::Module<T>>::block_number());
+        let (removed, filtered_council): (Seats<T::AccountId, BalanceOf<T>>, Seats<T::AccountId, BalanceOf<T>>) =
+            Self::active_council()
+                .into_iter()
+                .partition(|c| c.member == account_to_remove);
+        <ActiveCouncil<T>>::put(filtered_council);
+        <RemovedSeats<T>>::mutate(|seats| seats.extend(removed));
+        <ParticipationOf<T>>::remove(&account_to_remove);
+        Ok(())
+    }
+
+    /// Called when an election starts and takes over stakes of the council.
+    pub fn take_removed_seats() -> Seats<T::AccountId, BalanceOf<T>> {
+        <RemovedSeats<T>>::take()
+    }
+
+    /// Called when a proposal has been finalized. Every current councilor was eligible to vote on it.
+    pub fn record_proposal_participation(voters: &[T::AccountId]) {
+        for seat in Self::active_council() {
+            let councilor = seat.member;
+            let mut participation = Self::participation_of(&councilor);
+            participation.eligible += 1;
+            if voters.contains(&councilor) {
+                participation.voted += 1;
+            }
+            <ParticipationOf<T>>::insert(&councilor, &participation);
+            Self::check_participation(councilor, participation);
+        }
+    }
+
+    fn check_participation(councilor: T::AccountId, participation: Participation) {
+        let min_percent = Self::min_participation_percent();
+        let Participation { eligible, voted } = participation;
+        if min_percent == 0 || eligible < Self::min_eligible_proposals() || voted * 100 >= min_percent * eligible {
+            return;
+        }
+
+        Self::deposit_event(RawEvent::CouncilorInactive(councilor.clone(), voted, eligible));
+
+        // Start counting again, so a penalty is not applied on every next proposal.
+        <ParticipationOf<T>>::remove(&councilor);
+
+        match Self::inactivity_penalty() {
+            InactivityPenalty::NoPenalty => {},
+            InactivityPenalty::Slash(amount) => {
+                let mut seats = Self::active_council();
+                if let Some(seat) = seats.iter_mut().find(|seat| seat.member == councilor) {
+                    let slash = rstd::cmp::min(amount, seat.stake);
+                    seat.stake = seat.stake - slash;
+                    let _ = T::Currency::slash_reserved(&councilor, slash);
+                    Self::deposit_event(RawEvent::CouncilorSlashedForInactivity(councilor, slash));
+                }
+                <ActiveCouncil<T>>::put(seats);
+            },
+            InactivityPenalty::Removal => {
+                if Self::try_remove_council_member(councilor.clone()).is_ok() {
+                    Self::deposit_event(RawEvent::CouncilorRemovedForInactivity(councilor));
+                }
+            },
+        }
+    }
+
+    /// Pay every councilor a reward for blocks since the last payout.
+    fn pay_council_rewards(now: T::BlockNumber) {
+        let blocks = now - Self::last_reward_payout_at();
+        <LastRewardPayoutAt<T>>::put(now);
+        <NextRewardPayoutAt<T>>::put(now + Self::reward_payout_interval());
+
+        let blocks = BalanceOf::<T>::sa(blocks.as_());
+        let base_reward = Self::reward_per_block() * blocks;
+        let stake_bonus = Self::stake_bonus_per_block() * blocks;
+
+        let council = Self::active_council();
+        let council_stake = council.iter()
+            .fold(BalanceOf::<T>::zero(), |acc, seat| acc + seat.calc_total_stake());
+
+        for seat in council {
+            let mut reward = base_reward;
+            if !council_stake.is_zero() {
+                let seat_stake = seat.calc_total_stake();
+                // Seat stake is a part of council stake, so dividing first cannot overflow.
+                let bonus = stake_bonus.checked_mul(&seat_stake)
+                    .map(|product| product / council_stake)
+                    .unwrap_or_else(|| stake_bonus / council_stake * seat_stake);
+                reward = reward + bonus;
+            }
+            if reward.is_zero() {
+                continue;
+            }
+
+            match Self::pay_reward(&seat.member, reward) {
+                Ok(_) => Self::deposit_event(RawEvent::CouncilorRewarded(seat.member, reward)),
+                Err(_) => Self::deposit_event(RawEvent::CouncilorRewardNotPaid(seat.member, reward)),
+            }
+        }
+    }
+
+    fn pay_reward(councilor: &T::AccountId, amount: BalanceOf<T>) -> Result {
+        match Self::reward_treasury() {
+            Some(treasury) => {
+                ensure!(T::Currency::free_balance(&treasury) >= amount, "reward treasury balance is too low");
+                T::Currency::reserve(&treasury, amount)?;
+                if let Err(e) = T::Currency::repatriate_reserved(&treasury, councilor, amount) {
+                    let _ = T::Currency::unreserve(&treasury, amount);
+                    return Err(e);
+                }
+                Ok(())
+            },
+            None => T::Currency::reward(councilor, amount),
+        }
+    }
+
+    fn reset_participation() {
+        for seat in Self::active_council() {
+            <ParticipationOf<T>>::remove(&seat.member);
+        }
+    }
 }
 
 decl_module! {
@@ -75,12 +340,16 @@ decl_module! {
                 Self::deposit_event(RawEvent::CouncilTermEnded(now));
                 T::CouncilTermEnded::council_term_ended();
             }
+            if now >= Self::next_reward_payout_at() {
+                Self::pay_council_rewards(now);
+            }
         }
 
-        // Sudo methods...
+        // Sudo or council methods...
 
         /// Force set a zero staked council. Stakes in existing council will vanish into thin air!
-        fn set_council(accounts: Vec<T::AccountId>) {
+        fn set_council(origin, accounts: Vec<T::AccountId>) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             let new_council: Seats<T::AccountId, BalanceOf<T>> = accounts.into_iter().map(|account| {
                 Seat {
                     member: account,
@@ -88,36 +357,66 @@ decl_module! {
                     backers: vec![]
                 }
             }).collect();
+            Self::pay_council_rewards(<system::Module<T>>::block_number());
+            Self::reset_participation();
             <ActiveCouncil<T>>::put(new_council);
         }
 
         /// Adds a zero staked council member
-        fn add_council_member(account: T::AccountId) {
-            ensure!(!Self::is_councilor(&account), "cannot add same account multiple times");
-            let seat = Seat {
-                member: account,
-                stake: BalanceOf::<T>::zero(),
-                backers: vec![]
-            };
-
-            // add member to existing council
-            <ActiveCouncil<T>>::mutate(|council| council.push(seat));
+        fn add_council_member(origin, account: T::AccountId) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            Self::try_add_council_member(account)?;
         }
 
-        fn remove_council_member(account_to_remove: T::AccountId) {
-            ensure!(Self::is_councilor(&account_to_remove), "account is not a councilor");
-            let filtered_council: Seats<T::AccountId, BalanceOf<T>> = Self::active_council()
-                .into_iter()
-                .filter(|c| c.member != account_to_remove)
-                .collect();
-            <ActiveCouncil<T>>::put(filtered_council);
+        fn remove_council_member(origin, account_to_remove: T::AccountId) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            Self::try_remove_council_member(account_to_remove)?;
         }
 
         /// Set blocknumber when council term will end
-        fn set_term_ends_at(ends_at: T::BlockNumber) {
+        fn set_term_ends_at(origin, ends_at: T::BlockNumber) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(ends_at > <system::Module<T>>::block_number(), "must set future block number");
             <TermEndsAt<T>>::put(ends_at);
         }
+
+        /// Set a min participation of councilors in voting on proposals
+        /// and a penalty for those who participate less. Zero percent disables the checks.
+        fn set_participation_rules(
+            origin,
+            min_percent: u32,
+            min_eligible_proposals: u32,
+            penalty: InactivityPenalty<BalanceOf<T>>
+        ) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(min_percent <= 100, "min participation percent cannot be greater than 100");
+            <MinParticipationPercent<T>>::put(min_percent);
+            <MinEligibleProposals<T>>::put(min_eligible_proposals);
+            <InactivityPenaltyOf<T>>::put(penalty);
+        }
+
+        /// Rewards earned under previous parameters are paid out before the new ones apply.
+        fn set_reward_params(
+            origin,
+            reward_per_block: BalanceOf<T>,
+            stake_bonus_per_block: BalanceOf<T>,
+            payout_interval: T::BlockNumber
+        ) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(!payout_interval.is_zero(), "payout interval must be greater than zero");
+
+            Self::pay_council_rewards(<system::Module<T>>::block_number());
+            <RewardPerBlock<T>>::put(reward_per_block);
+            <StakeBonusPerBlock<T>>::put(stake_bonus_per_block);
+            <RewardPayoutInterval<T>>::put(payout_interval);
+            <NextRewardPayoutAt<T>>::put(<system::Module<T>>::block_number() + payout_interval);
+        }
+
+        /// Pay rewards from a given account or mint them if no account is given.
+        fn set_reward_treasury(origin, treasury: Option<T::AccountId>) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            <RewardTreasury<T>>::put(treasury);
+        }
     }
 }
 
@@ -125,6 +424,7 @@ decl_module! {
 mod tests {
     use super::*;
     use crate::governance::mock::*;
+    use crate::governance::mock::Origin;
     use parity_codec::Encode;
     use runtime_io::with_externalities;
     use srml_support::*;
@@ -134,10 +434,10 @@ mod tests {
         with_externalities(&mut initial_test_ext(), || {
             assert!(!Council::is_councilor(&1));
 
-            assert_ok!(Council::add_council_member(1));
+            assert_ok!(Council::add_council_member(Origin::ROOT, 1));
             assert!(Council::is_councilor(&1));
 
-            assert_ok!(Council::add_council_member(2));
+            assert_ok!(Council::add_council_member(Origin::ROOT, 2));
             assert!(Council::is_councilor(&1));
             assert!(Council::is_councilor(&2));
         });
@@ -146,11 +446,11 @@ mod tests {
     #[test]
     fn remove_council_member_test() {
         with_externalities(&mut initial_test_ext(), || {
-            assert_ok!(Council::add_council_member(1));
-            assert_ok!(Council::add_council_member(2));
-            assert_ok!(Council::add_council_member(3));
+            assert_ok!(Council::add_council_member(Origin::ROOT, 1));
+            assert_ok!(Council::add_council_member(Origin::ROOT, 2));
+            assert_ok!(Council::add_council_member(Origin::ROOT, 3));
 
-            assert_ok!(Council::remove_council_member(2));
+            assert_ok!(Council::remove_council_member(Origin::ROOT, 2));
 
             assert!(!Council::is_councilor(&2));
             assert!(Council::is_councilor(&1));
@@ -158,10 +458,188 @@ mod tests {
         });
     }
 
+    #[test]
+    fn council_majority_can_make_privileged_calls() {
+        with_externalities(&mut initial_test_ext(), || {
+            assert_ok!(Council::add_council_member(Origin::from(RawOrigin::Members(3, 5)), 1));
+            assert!(Council::is_councilor(&1));
+
+            assert!(Council::add_council_member(Origin::from(RawOrigin::Members(2, 4)), 2).is_err());
+            assert!(Council::add_council_member(Origin::signed(1), 2).is_err());
+            assert!(!Council::is_councilor(&2));
+        });
+    }
+
+    #[test]
+    fn participation_is_recorded_and_reset_on_new_council() {
+        with_externalities(&mut initial_test_ext(), || {
+            assert_ok!(Council::set_council(Origin::ROOT, vec![1, 2, 3]));
+
+            Council::record_proposal_participation(&[1, 2]);
+            Council::record_proposal_participation(&[1]);
+
+            assert_eq!(Council::participation_of(1), Participation { eligible: 2, voted: 2 });
+            assert_eq!(Council::participation_of(2), Participation { eligible: 2, voted: 1 });
+            assert_eq!(Council::participation_of(3), Participation { eligible: 2, voted: 0 });
+
+            assert_ok!(Council::set_council(Origin::ROOT, vec![1, 4]));
+            assert_eq!(Council::participation_of(1), Participation::default());
+        });
+    }
+
+    #[test]
+    fn inactive_councilor_is_removed() {
+        with_externalities(&mut initial_test_ext(), || {
+            assert_ok!(Council::set_council(Origin::ROOT, vec![1, 2]));
+            assert_ok!(Council::set_participation_rules(Origin::ROOT, 50, 2, InactivityPenalty::Removal));
+
+            // Not enough proposals to judge yet:
+            Council::record_proposal_participation(&[1]);
+            assert!(Council::is_councilor(&2));
+
+            Council::record_proposal_participation(&[1]);
+            assert!(Council::is_councilor(&1));
+            assert!(!Council::is_councilor(&2));
+        });
+    }
+
+    #[test]
+    fn stakes_of_removed_councilor_are_transferred_to_next_election() {
+        with_externalities(&mut initial_test_ext(), || {
+            let backer = election::Backer { member: 3, stake: 50 };
+            let seats = vec![
+                Seat { member: 1, stake: 100, backers: vec![] },
+                Seat { member: 2, stake: 100, backers: vec![backer] },
+            ];
+            Council::council_elected(seats, 10);
+            for account in &[1, 2] {
+                Balances::set_free_balance(account, 10);
+                Balances::set_reserved_balance(account, 100);
+            }
+            Balances::set_free_balance(&3, 10);
+            Balances::set_reserved_balance(&3, 50);
+            assert_ok!(Council::set_participation_rules(Origin::ROOT, 50, 1, InactivityPenalty::Removal));
+
+            Council::record_proposal_participation(&[1]);
+
+            // Stakes are neither slashed nor lost:
+            assert!(!Council::is_councilor(&2));
+            assert_eq!(Council::removed_seats().len(), 1);
+            assert_eq!(Balances::reserved_balance(&2), 100);
+            assert_eq!(Balances::free_balance(&2), 10);
+            assert_eq!(Balances::reserved_balance(&3), 50);
+            assert_eq!(Balances::free_balance(&3), 10);
+
+            assert_ok!(Election::force_start_election(Origin::ROOT));
+            assert!(Council::removed_seats().is_empty());
+            assert_eq!(Election::existing_stake_holders(), vec![1, 2, 3]);
+        });
+    }
+
+    #[test]
+    fn inactive_councilor_is_slashed() {
+        with_externalities(&mut initial_test_ext(), || {
+            let seats = vec![1, 2].into_iter().map(|member| Seat { member, stake: 100, backers: vec![] }).collect();
+            Council::council_elected(seats, 10);
+            Balances::set_reserved_balance(&2, 100);
+            assert_ok!(Council::set_participation_rules(Origin::ROOT, 50, 1, InactivityPenalty::Slash(30)));
+
+            Council::record_proposal_participation(&[1]);
+
+            assert!(Council::is_councilor(&2));
+            assert_eq!(Council::active_council()[1].stake, 70);
+            assert_eq!(Balances::reserved_balance(&2), 70);
+            assert_eq!(Council::participation_of(2), Participation::default());
+            assert_eq!(Council::active_council()[0].stake, 100);
+        });
+    }
+
+    #[test]
+    fn rewards_are_minted_at_payout_interval() {
+        with_externalities(&mut initial_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(Council::set_council(Origin::ROOT, vec![1, 2]));
+            Balances::set_free_balance(&1, 1);
+            Balances::set_free_balance(&2, 1);
+            assert_ok!(Council::set_reward_params(Origin::ROOT, 10, 0, 5));
+
+            System::set_block_number(5);
+            Council::on_finalise(5);
+            assert_eq!(Balances::free_balance(&1), 1);
+
+            System::set_block_number(6);
+            Council::on_finalise(6);
+            assert_eq!(Balances::free_balance(&1), 51);
+            assert_eq!(Balances::free_balance(&2), 51);
+            assert_eq!(Council::next_reward_payout_at(), 11);
+
+            // Outgoing councilors are paid when a new council is set:
+            System::set_block_number(8);
+            assert_ok!(Council::set_council(Origin::ROOT, vec![3]));
+            assert_eq!(Balances::free_balance(&1), 71);
+            assert_eq!(Balances::free_balance(&3), 0);
+        });
+    }
+
+    #[test]
+    fn rewards_are_paid_before_council_member_is_added_or_removed() {
+        with_externalities(&mut initial_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(Council::set_council(Origin::ROOT, vec![1, 2]));
+            for account in &[1, 2, 3] {
+                Balances::set_free_balance(account, 1);
+            }
+            assert_ok!(Council::set_reward_params(Origin::ROOT, 10, 0, 100));
+
+            System::set_block_number(4);
+            assert_ok!(Council::add_council_member(Origin::ROOT, 3));
+            assert_eq!(Balances::free_balance(&1), 31);
+            assert_eq!(Balances::free_balance(&3), 1);
+
+            System::set_block_number(6);
+            assert_ok!(Council::remove_council_member(Origin::ROOT, 1));
+            assert_eq!(Balances::free_balance(&1), 51);
+            assert_eq!(Balances::free_balance(&2), 51);
+            assert_eq!(Balances::free_balance(&3), 21);
+        });
+    }
+
+    #[test]
+    fn rewards_with_stake_bonus_are_paid_from_treasury() {
+        with_externalities(&mut initial_test_ext(), || {
+            System::set_block_number(1);
+            let seats = vec![(1, 100), (2, 300)].into_iter()
+                .map(|(member, stake)| Seat { member, stake, backers: vec![] })
+                .collect();
+            Council::council_elected(seats, 100);
+            Balances::set_free_balance(&1, 1);
+            Balances::set_free_balance(&2, 1);
+
+            let treasury = 10;
+            Balances::set_free_balance(&treasury, 275);
+            assert_ok!(Council::set_reward_treasury(Origin::ROOT, Some(treasury)));
+            assert_ok!(Council::set_reward_params(Origin::ROOT, 10, 20, 5));
+
+            System::set_block_number(6);
+            Council::on_finalise(6);
+
+            // 5 blocks of base reward and a bonus split 1:3 by stake:
+            assert_eq!(Balances::free_balance(&1), 1 + 50 + 25);
+            assert_eq!(Balances::free_balance(&2), 1 + 50 + 75);
+            assert_eq!(Balances::free_balance(&treasury), 75);
+
+            // The treasury cannot cover the next payout of the second councilor:
+            System::set_block_number(11);
+            Council::on_finalise(11);
+            assert_eq!(Balances::free_balance(&1), 1 + 75 * 2);
+            assert_eq!(Balances::free_balance(&2), 1 + 125);
+        });
+    }
+
     #[test]
     fn set_council_test() {
         with_externalities(&mut initial_test_ext(), || {
-            assert_ok!(Council::set_council(vec![4,5,6]));
+            assert_ok!(Council::set_council(Origin::ROOT, vec![4,5,6]));
             assert!(Council::is_councilor(&4));
             assert!(Council::is_councilor(&5));
             assert!(Council::is_councilor(&6));
@@ -5,22 +5,135 @@ use srml_support::{StorageValue, StorageMap, dispatch::Result, decl_module, decl
 use srml_support::traits::{Currency};
 use system::{self, ensure_signed};
 
-use runtime_primitives::traits::{Hash, As, Zero, /*SimpleArithmetic*/};
+use runtime_primitives::traits::{Hash, As, Zero, Saturating, SimpleArithmetic};
 //use {balances};
 
 use rstd::collections::btree_map::BTreeMap;
 use rstd::ops::Add;
+use rstd::cmp::Reverse;
 
 use super::stake::Stake;
 use super::sealed_vote::SealedVote;
 
 pub use super::{ GovernanceCurrency, BalanceOf };
-use super::council;
+use super::council::{self, EnsureCouncilOrigin};
+use crate::traits::Members;
 
 pub trait Trait: system::Trait + council::Trait + GovernanceCurrency {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
     type CouncilElected: CouncilElected<Seats<Self::AccountId, BalanceOf<Self>>, Self::BlockNumber>;
+
+    type Members: Members<Self>;
+}
+
+/// How seats are allocated when there are more applicants than seats.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
+pub enum SeatSelection {
+    /// Applicants with the most stake behind them, after ranked votes are redistributed.
+    TopStaked,
+    /// Sequential Phragmén, which spreads the stake of each vote over all elected applicants it ranks.
+    Phragmen,
+}
+
+impl Default for SeatSelection {
+    fn default() -> Self {
+        SeatSelection::TopStaked
+    }
+}
+
+/// Fixed point scale of voter loads in sequential Phragmén. Budgets add up to less than 2^32,
+/// so a load of one round keeps at least 48 bits of precision. A load grows by at most the scale
+/// in every round, so budget times load fits into u128 for up to 2^16 seats.
+const PHRAGMEN_LOAD_SCALE: u128 = 1 << 80;
+
+/// A voter in sequential Phragmén. Own stake of an applicant is counted as a voter that only backs the applicant.
+struct PhragmenVoter<AccountId, Balance> {
+    who: AccountId,
+    stake: Balance,
+    // stake scaled down by `phragmen_divisor`
+    budget: u128,
+    load: u128,
+    approvals: Vec<AccountId>,
+    // load put on the voter by each elected applicant it backs
+    edges: Vec<(AccountId, u128)>,
+    is_applicant: bool,
+}
+
+/// Balances convert to numbers only through u64, and loads need headroom over budgets.
+/// Every stake is divided by the same divisor, so that the total budget is below 2^32
+/// and proportions between stakes are kept.
+fn phragmen_divisor<Balance: SimpleArithmetic + Copy>(total_stake: Balance) -> Balance {
+    total_stake / Balance::sa(u32::max_value() as u64) + Balance::sa(1)
+}
+
+/// Elects up to `seats` applicants in sequential Phragmén, putting loads on `voters`.
+/// The applicant with the least score wins a round, and the greater tie breaker wins a tie.
+fn phragmen_elect<AccountId, Balance, K, F>(
+    applicants: &[AccountId],
+    voters: &mut [PhragmenVoter<AccountId, Balance>],
+    seats: usize,
+    tie_breaker: F) -> Vec<AccountId>
+where
+    AccountId: Ord + Clone,
+    K: Ord,
+    F: Fn(&AccountId, usize) -> K,
+{
+    // Voters backing each applicant, so a round only visits voters of every applicant once.
+    let mut supporters: BTreeMap<AccountId, Vec<usize>> = BTreeMap::new();
+    for (i, voter) in voters.iter().enumerate() {
+        for applicant in voter.approvals.iter() {
+            supporters.entry(applicant.clone()).or_insert_with(Vec::new).push(i);
+        }
+    }
+    let no_supporters = vec![];
+
+    let mut elected: Vec<AccountId> = Vec::new();
+
+    while elected.len() < seats {
+        // elect the applicant that would put the least load on its voters
+        let mut winner: Option<(AccountId, u128, usize)> = None;
+
+        for applicant in applicants.iter().filter(|applicant| !elected.contains(applicant)) {
+            let applicant_supporters = supporters.get(applicant).unwrap_or(&no_supporters);
+            let (approval_stake, weighted_load) = applicant_supporters.iter()
+                .map(|&i| &voters[i])
+                .fold((0u128, 0u128), |(stake, load), voter| (
+                    stake.saturating_add(voter.budget),
+                    load.saturating_add(voter.budget.saturating_mul(voter.load))
+                ));
+
+            if approval_stake == 0 {
+                continue;
+            }
+
+            let score = PHRAGMEN_LOAD_SCALE.saturating_add(weighted_load) / approval_stake;
+            let backers = applicant_supporters.len();
+            let is_better = match winner.as_ref() {
+                None => true,
+                Some((best, best_score, best_backers)) => score < *best_score || (score == *best_score
+                    && tie_breaker(applicant, backers) > tie_breaker(best, *best_backers)),
+            };
+            if is_better {
+                winner = Some((applicant.clone(), score, backers));
+            }
+        }
+
+        match winner {
+            Some((applicant, score, _)) => {
+                for &i in supporters.get(&applicant).unwrap_or(&no_supporters) {
+                    let voter = &mut voters[i];
+                    voter.edges.push((applicant.clone(), score.saturating_sub(voter.load)));
+                    voter.load = score;
+                }
+                elected.push(applicant);
+            },
+            None => break,
+        }
+    }
+
+    elected
 }
 
 #[derive(Clone, Copy, Encode, Decode)]
@@ -93,12 +206,17 @@ decl_storage! {
 
         Applicants get(applicants): Vec<T::AccountId>;
         ApplicantStakes get(applicant_stakes): map T::AccountId => Stake<BalanceOf<T>>;
+        /// Block at which an applicant first applied in the current election.
+        ApplicantAppliedAt get(applicant_applied_at): map T::AccountId => T::BlockNumber;
 
         Commitments get(commitments): Vec<T::Hash>;
 
         // TODO value type of this map looks scary, is there any way to simplify the notation?
         Votes get(votes): map T::Hash => SealedVote<T::AccountId, Stake<BalanceOf<T>>, T::Hash, T::AccountId>;
 
+        /// Applicants in order of preference for revealed ranked ballots.
+        RankedVotes get(ranked_votes): map T::Hash => Vec<T::AccountId>;
+
         // Current Election Parameters - default "zero" values are not meaningful. Running an election without
         // settings reasonable values is a bad idea. Parameters can be set in the TriggerElection hook.
         AnnouncingPeriod get(announcing_period) config(): T::BlockNumber = T::BlockNumber::sa(100);
@@ -109,6 +227,15 @@ decl_storage! {
         MinCouncilStake get(min_council_stake) config(): BalanceOf<T> = BalanceOf::<T>::sa(100);
         NewTermDuration get(new_term_duration) config(): T::BlockNumber = T::BlockNumber::sa(1000);
         MinVotingStake get(min_voting_stake) config(): BalanceOf<T> = BalanceOf::<T>::sa(10);
+        SeatSelectionMethod get(seat_selection) config(): SeatSelection = SeatSelection::TopStaked;
+
+        /// Percentage of a voting stake that is slashed if the vote was never revealed.
+        UnrevealedVoteSlashPercent get(unrevealed_vote_slash_percent) config(): u32 = 0;
+        /// Percentage of a voting stake that is slashed if the vote was for an applicant that did not get elected.
+        /// Cannot be greater than the slash for unrevealed votes.
+        LosingVoteSlashPercent get(losing_vote_slash_percent) config(): u32 = 0;
+        /// Account that receives slashed voting stakes. Slashed stakes are burned if not set.
+        SlashedStakeRecipient get(slashed_stake_recipient): Option<T::AccountId>;
     }
 }
 
@@ -117,7 +244,8 @@ decl_event!(
     pub enum Event<T> where
     <T as system::Trait>::BlockNumber,
     <T as system::Trait>::AccountId,
-    <T as system::Trait>::Hash  {
+    <T as system::Trait>::Hash,
+    Balance = BalanceOf<T> {
         /// A new election started
         ElectionStarted(),
         AnnouncingStarted(u32),
@@ -128,8 +256,14 @@ decl_event!(
         RevealingEnded(),
         CouncilElected(BlockNumber),
         Applied(AccountId),
+        ApplicantWithdrew(AccountId),
         Voted(AccountId, Hash),
         Revealed(AccountId, Hash, AccountId),
+        RankedVoteRevealed(AccountId, Hash, Vec<AccountId>),
+        /// Voter, commitment and slashed amount of a vote that was never revealed
+        UnrevealedVoteSlashed(AccountId, Hash, Balance),
+        /// Voter, commitment and slashed amount of a vote for an applicant that was not elected
+        LosingVoteSlashed(AccountId, Hash, Balance),
     }
 );
 
@@ -148,9 +282,8 @@ impl<T: Trait> Module<T> {
         <system::Module<T>>::block_number() + length
     }
 
-    // TODO This method should be moved to Membership module once it's created.
     fn is_member(sender: T::AccountId) -> bool {
-        !T::Currency::free_balance(&sender).is_zero()
+        T::Members::is_active_member(&sender)
     }
 
     // PUBLIC IMMUTABLES
@@ -183,6 +316,10 @@ impl<T: Trait> Module<T> {
         ensure!(Self::applicants().len() == 0, "applicants must be empty");
         ensure!(Self::commitments().len() == 0, "commitments must be empty");
 
+        // Stakes of councilors removed during the term are transferable too.
+        let mut current_council = current_council;
+        current_council.extend(<council::Module<T>>::take_removed_seats());
+
         // Take snapshot of seat and backing stakes of an existing council
         // Its important to note that the election system takes ownership of these stakes, and is responsible
         // to return any unused stake to original owners and the end of the election.
@@ -270,29 +407,26 @@ impl<T: Trait> Module<T> {
             votes.push(Self::votes(commitment));
         }
 
-        let mut new_council = Self::tally_votes(&votes);
+        let mut new_council = Self::tally_votes(&votes, &[]);
 
         // Note here that applicants with zero votes dont appear in the tally.
         // Is an applicant with some votes but less total stake than another applicant with zero votes
         // more qualified to be on the council?
         // Consider implications - if a council can be formed purely by staking are we fine with that?
-
-        for applicant in Self::applicants().iter() {
-            if !new_council.contains_key(applicant) {
-                new_council.insert(applicant.clone(), Seat {
-                    member: applicant.clone(),
-                    stake: Self::applicant_stakes(applicant).total(),
-                    backers: Vec::new(),
-                });
-            }
-        }
+        Self::add_unvoted_applicants(&mut new_council, &[]);
 
         if new_council.len() == Self::council_size_usize() {
             // all applicants in the tally will form the new council
         } else if new_council.len() > Self::council_size_usize() {
             // we have more than enough applicants to form the new council.
-            // select top staked
-            Self::filter_top_staked(&mut new_council, Self::council_size_usize());
+            match Self::seat_selection() {
+                SeatSelection::TopStaked => {
+                    Self::instant_runoff(&votes, &mut new_council, Self::council_size_usize());
+                },
+                SeatSelection::Phragmen => {
+                    new_council = Self::phragmen_seats(&votes, Self::council_size_usize());
+                },
+            }
         } else {
             // Not enough applicants with votes to form a council.
             // This may happen if we didn't add applicants with zero votes to the tally,
@@ -306,7 +440,8 @@ impl<T: Trait> Module<T> {
         Self::teardown_election (
             &votes,
             &new_council,
-            true /* unlock transferable stakes */
+            true, /* unlock transferable stakes */
+            true /* slash unrevealed and losing votes */
         );
 
         let new_council = new_council.into_iter().map(|(_, seat)| seat).collect();
@@ -318,9 +453,10 @@ impl<T: Trait> Module<T> {
     fn teardown_election (
         votes: &Vec<SealedVote<T::AccountId, Stake<BalanceOf<T>>,
         T::Hash, T::AccountId>>, new_council: &BTreeMap<T::AccountId, Seat<T::AccountId, BalanceOf<T>>>,
-        unlock_ts: bool)
+        unlock_ts: bool,
+        slash_votes: bool)
     {
-        Self::refund_voting_stakes(&votes, &new_council);
+        Self::refund_voting_stakes(&votes, &new_council, slash_votes);
         Self::clear_votes();
 
         Self::drop_unelected_applicants(&new_council);
@@ -355,7 +491,8 @@ impl<T: Trait> Module<T> {
 
     fn clear_applicants() {
         for applicant in Self::applicants() {
-            <ApplicantStakes<T>>::remove(applicant);
+            <ApplicantStakes<T>>::remove(&applicant);
+            <ApplicantAppliedAt<T>>::remove(&applicant);
         }
         <Applicants<T>>::kill();
     }
@@ -382,6 +519,7 @@ impl<T: Trait> Module<T> {
         for applicant in drop {
             Self::refund_applicant(applicant);
             <ApplicantStakes<T>>::remove(applicant);
+            <ApplicantAppliedAt<T>>::remove(applicant);
         }
 
         <Applicants<T>>::put(not_dropped);
@@ -397,45 +535,95 @@ impl<T: Trait> Module<T> {
 
     fn refund_voting_stakes(
         sealed_votes: &Vec<SealedVote<T::AccountId, Stake<BalanceOf<T>>, T::Hash, T::AccountId>>,
-        new_council: &BTreeMap<T::AccountId, Seat<T::AccountId, BalanceOf<T>>>)
+        new_council: &BTreeMap<T::AccountId, Seat<T::AccountId, BalanceOf<T>>>,
+        slash_votes: bool)
     {
         for sealed_vote in sealed_votes.iter() {
             // Do a refund if commitment was not revealed, or the vote was for applicant that did
-            // not get elected to the council
-            // TODO critical: shouldn't we slash the stake in such a case? This is the whole idea behid staking on something: people need to decide carefully and be responsible for their bahavior because they can loose their stake
-            // See https://github.com/Joystream/substrate-node-joystream/issues/4
-            let do_refund = match sealed_vote.get_vote() {
-                Some(applicant) => !new_council.contains_key(&applicant),
-                None => true
+            // not get elected to the council. Voters are responsible for their votes, so part of
+            // the stake is slashed first. A ranked vote backs its most preferred elected applicant.
+            let slash_percent = if sealed_vote.is_not_revealed() {
+                Self::unrevealed_vote_slash_percent()
+            } else if Self::ranking_of(sealed_vote).iter().any(|applicant| new_council.contains_key(applicant)) {
+                continue
+            } else {
+                Self::losing_vote_slash_percent()
             };
 
-            if do_refund {
-                // return new stake to account's free balance
-                let SealedVote { voter, stake, .. } = sealed_vote;
-                if !stake.new.is_zero() {
-                    T::Currency::unreserve(voter, stake.new);
-                }
+            let SealedVote { voter, stake, commitment, .. } = sealed_vote;
 
-                // return unused transferable stake
-                if !stake.transferred.is_zero() {
-                    <TransferableStakes<T>>::mutate(voter, |transferable| (*transferable).backing += stake.transferred);
+            let mut slash = BalanceOf::<T>::zero();
+            if slash_votes {
+                slash = stake.total() * BalanceOf::<T>::sa(slash_percent as u64) / BalanceOf::<T>::sa(100);
+            }
+
+            // slash new stake first, then the transferred stake
+            let slash_new = rstd::cmp::min(slash, stake.new);
+            let slash_transferred = slash - slash_new;
+
+            if !slash.is_zero() {
+                Self::slash_voting_stake(voter, slash);
+
+                if sealed_vote.is_revealed() {
+                    Self::deposit_event(RawEvent::LosingVoteSlashed(voter.clone(), *commitment, slash));
+                } else {
+                    Self::deposit_event(RawEvent::UnrevealedVoteSlashed(voter.clone(), *commitment, slash));
                 }
             }
+
+            // return new stake to account's free balance
+            let refund_new = stake.new - slash_new;
+            if !refund_new.is_zero() {
+                T::Currency::unreserve(voter, refund_new);
+            }
+
+            // return unused transferable stake
+            let refund_transferred = stake.transferred - slash_transferred;
+            if !refund_transferred.is_zero() {
+                <TransferableStakes<T>>::mutate(voter, |transferable| (*transferable).backing += refund_transferred);
+            }
+        }
+    }
+
+    /// Moves slashed reserved stake of a voter to the slashed stake recipient, or burns it.
+    fn slash_voting_stake(voter: &T::AccountId, amount: BalanceOf<T>) {
+        if let Some(recipient) = Self::slashed_stake_recipient() {
+            if T::Currency::repatriate_reserved(voter, &recipient, amount).is_ok() {
+                return;
+            }
         }
+        T::Currency::slash_reserved(voter, amount);
     }
 
     fn clear_votes() {
         for commitment in Self::commitments() {
             <Votes<T>>::remove(commitment);
+            <RankedVotes<T>>::remove(commitment);
         }
         <Commitments<T>>::kill();
     }
 
-    fn tally_votes(sealed_votes: &Vec<SealedVote<T::AccountId, Stake<BalanceOf<T>>, T::Hash, T::AccountId>>) -> BTreeMap<T::AccountId, Seat<T::AccountId, BalanceOf<T>>> {
+    /// Returns applicants in order of preference of a revealed vote. A vote for a single applicant
+    /// is a ranking of length one.
+    fn ranking_of(sealed_vote: &SealedVote<T::AccountId, Stake<BalanceOf<T>>, T::Hash, T::AccountId>) -> Vec<T::AccountId> {
+        let ranking = Self::ranked_votes(&sealed_vote.commitment);
+        if ranking.is_empty() {
+            sealed_vote.get_vote().iter().cloned().collect()
+        } else {
+            ranking
+        }
+    }
+
+    /// Counts the stake of each revealed vote for its most preferred applicant that was not eliminated.
+    fn tally_votes(
+        sealed_votes: &Vec<SealedVote<T::AccountId, Stake<BalanceOf<T>>, T::Hash, T::AccountId>>,
+        eliminated: &[T::AccountId]) -> BTreeMap<T::AccountId, Seat<T::AccountId, BalanceOf<T>>>
+    {
         let mut tally: BTreeMap<T::AccountId, Seat<T::AccountId, BalanceOf<T>>> = BTreeMap::new();
 
         for sealed_vote in sealed_votes.iter() {
-            if let Some(applicant) = sealed_vote.get_vote() {
+            let ranking = Self::ranking_of(sealed_vote);
+            if let Some(applicant) = ranking.iter().find(|applicant| !eliminated.contains(applicant)) {
                 if !tally.contains_key(&applicant) {
                     // Add new seat
                     tally.insert(applicant.clone(), Seat {
@@ -457,6 +645,159 @@ impl<T: Trait> Module<T> {
         tally
     }
 
+    /// Eliminates the least staked applicant one at a time until `seats` remain in the tally.
+    /// Ballots of an eliminated applicant move to their next preferred applicant that is still
+    /// in the tally, and other ballots stay where they are, so a round only touches the ballots
+    /// of one applicant.
+    fn instant_runoff(
+        sealed_votes: &Vec<SealedVote<T::AccountId, Stake<BalanceOf<T>>, T::Hash, T::AccountId>>,
+        tally: &mut BTreeMap<T::AccountId, Seat<T::AccountId, BalanceOf<T>>>,
+        seats: usize)
+    {
+        let rankings: Vec<Vec<T::AccountId>> = sealed_votes.iter().map(|vote| Self::ranking_of(vote)).collect();
+
+        // Position of the counted preference in each ranking and ballots counted for each applicant.
+        let mut positions: Vec<usize> = vec![0; rankings.len()];
+        let mut ballots: BTreeMap<T::AccountId, Vec<usize>> = BTreeMap::new();
+        for (i, ranking) in rankings.iter().enumerate() {
+            if let Some(position) = ranking.iter().position(|applicant| tally.contains_key(applicant)) {
+                positions[i] = position;
+                ballots.entry(ranking[position].clone()).or_insert_with(Vec::new).push(i);
+            }
+        }
+
+        while tally.len() > seats {
+            let applicants: Vec<T::AccountId> = tally.keys().cloned().collect();
+            let remaining = tally.len() - 1;
+            Self::filter_top_staked(tally, remaining);
+
+            let eliminated = match applicants.into_iter().find(|applicant| !tally.contains_key(applicant)) {
+                Some(applicant) => applicant,
+                // The tally is inconsistent with the list of applicants.
+                None => break,
+            };
+
+            for i in ballots.remove(&eliminated).unwrap_or_default() {
+                let ranking = &rankings[i];
+                let next = (positions[i] + 1 .. ranking.len()).find(|&position| tally.contains_key(&ranking[position]));
+                if let Some(position) = next {
+                    positions[i] = position;
+                    let applicant = ranking[position].clone();
+                    if let Some(seat) = tally.get_mut(&applicant) {
+                        seat.backers.push(Backer {
+                            member: sealed_votes[i].voter.clone(),
+                            stake: sealed_votes[i].stake.total(),
+                        });
+                    }
+                    ballots.entry(applicant).or_insert_with(Vec::new).push(i);
+                }
+            }
+        }
+    }
+
+    /// Elects up to `seats` applicants with sequential Phragmén over revealed votes, where a vote backs
+    /// every applicant it ranks. Each elected applicant picks up the voters that back it, and stake of
+    /// a voter is split among the seats it backs in proportion to the load each seat put on the voter.
+    /// Applicants with equal scores are ordered like equally staked seats in `filter_top_staked`.
+    fn phragmen_seats(
+        sealed_votes: &Vec<SealedVote<T::AccountId, Stake<BalanceOf<T>>, T::Hash, T::AccountId>>,
+        seats: usize) -> BTreeMap<T::AccountId, Seat<T::AccountId, BalanceOf<T>>>
+    {
+        let applicants = Self::applicants();
+        let revealed_votes: Vec<_> = sealed_votes.iter().filter(|sealed_vote| sealed_vote.is_revealed()).collect();
+
+        let total_stake = applicants.iter()
+            .map(|applicant| Self::applicant_stakes(applicant).total())
+            .chain(revealed_votes.iter().map(|sealed_vote| sealed_vote.stake.total()))
+            .fold(BalanceOf::<T>::zero(), |total, stake| total.saturating_add(stake));
+        let divisor = phragmen_divisor(total_stake);
+        let budget_of = |stake: BalanceOf<T>| (stake / divisor).as_() as u128;
+
+        let mut voters: Vec<PhragmenVoter<T::AccountId, BalanceOf<T>>> = applicants.iter()
+            .map(|applicant| {
+                let stake = Self::applicant_stakes(applicant).total();
+                PhragmenVoter {
+                    who: applicant.clone(),
+                    stake,
+                    budget: budget_of(stake),
+                    load: 0,
+                    approvals: vec![applicant.clone()],
+                    edges: vec![],
+                    is_applicant: true,
+                }
+            })
+            .collect();
+
+        for sealed_vote in revealed_votes {
+            let stake = sealed_vote.stake.total();
+            voters.push(PhragmenVoter {
+                who: sealed_vote.voter.clone(),
+                stake,
+                budget: budget_of(stake),
+                load: 0,
+                approvals: Self::ranking_of(sealed_vote),
+                edges: vec![],
+                is_applicant: false,
+            });
+        }
+
+        let random_seed = <system::Module<T>>::random_seed();
+        let elected = phragmen_elect(&applicants, &mut voters, seats,
+            |applicant, backers| Self::tie_breaker(applicant, backers, random_seed));
+
+        let mut tally: BTreeMap<T::AccountId, Seat<T::AccountId, BalanceOf<T>>> = BTreeMap::new();
+
+        for applicant in elected.iter() {
+            tally.insert(applicant.clone(), Seat {
+                member: applicant.clone(),
+                stake: Self::applicant_stakes(applicant).total(),
+                backers: vec![],
+            });
+        }
+
+        for voter in voters.iter().filter(|voter| !voter.is_applicant && voter.load > 0) {
+            let mut remaining = voter.stake;
+
+            for (i, (applicant, edge_load)) in voter.edges.iter().enumerate() {
+                // the last seat gets what is left, so that no stake is lost to rounding
+                let stake = if i + 1 == voter.edges.len() {
+                    remaining
+                } else {
+                    // a share of the budget is not greater than the budget, so it fits into u32
+                    let share = voter.budget.saturating_mul(*edge_load) / voter.load;
+                    rstd::cmp::min(BalanceOf::<T>::sa(share as u64) * divisor, remaining)
+                };
+                remaining = remaining - stake;
+
+                if let Some(seat) = tally.get_mut(applicant) {
+                    if !stake.is_zero() {
+                        seat.backers.push(Backer {
+                            member: voter.who.clone(),
+                            stake,
+                        });
+                    }
+                }
+            }
+        }
+
+        tally
+    }
+
+    fn add_unvoted_applicants(tally: &mut BTreeMap<T::AccountId, Seat<T::AccountId, BalanceOf<T>>>, eliminated: &[T::AccountId]) {
+        for applicant in Self::applicants().iter() {
+            if !tally.contains_key(applicant) && !eliminated.contains(applicant) {
+                tally.insert(applicant.clone(), Seat {
+                    member: applicant.clone(),
+                    stake: Self::applicant_stakes(applicant).total(),
+                    backers: Vec::new(),
+                });
+            }
+        }
+    }
+
+    /// Keeps `limit` strongest seats in the tally. Seats are ordered by total stake, then by number of backers,
+    /// then by earliest application, and seats that are still tied are ordered by a hash of the applicant
+    /// and the random seed, so the outcome does not depend on the order of applicants.
     fn filter_top_staked(tally: &mut BTreeMap<T::AccountId, Seat<T::AccountId, BalanceOf<T>>>, limit: usize) {
 
         if limit >= tally.len() {
@@ -474,10 +815,13 @@ impl<T: Trait> Module<T> {
             return;
         }
 
-        // TODO: order by number of votes, then number of backers
+        let random_seed = <system::Module<T>>::random_seed();
 
+        // weakest seats first
         seats.sort_by_key(|applicant| {
-            tally.get(&applicant).map_or(Zero::zero(), |seat| seat.calc_total_stake())
+            let (total_stake, backers) = tally.get(&applicant)
+                .map_or((Zero::zero(), 0), |seat| (seat.calc_total_stake(), seat.backers.len()));
+            (total_stake, Self::tie_breaker(applicant, backers, random_seed))
         });
 
         // seats at bottom of list
@@ -488,6 +832,18 @@ impl<T: Trait> Module<T> {
         }
     }
 
+    /// Orders applicants with equal stake or Phragmén score, the greater the stronger: by number of backers,
+    /// then by earliest application, then by a hash of the applicant and the random seed.
+    fn tie_breaker(applicant: &T::AccountId, backers: usize, random_seed: T::Hash)
+        -> (usize, Reverse<T::BlockNumber>, Vec<u8>)
+    {
+        (
+            backers,
+            Reverse(Self::applicant_applied_at(applicant)),
+            T::Hashing::hash_of(&(random_seed, applicant.clone())).as_ref().to_vec(),
+        )
+    }
+
     /// Checks if the current election stage has ended and calls the stage ended handler
     fn check_if_stage_is_ending(now: T::BlockNumber) {
         if let Some(stage) = Self::stage() {
@@ -587,6 +943,7 @@ impl<T: Trait> Module<T> {
             // insert element at the begining, this gives priority to early applicants
             // when ordering applicants by stake if stakes are equal
             <Applicants<T>>::mutate(|applicants| applicants.insert(0, applicant.clone()));
+            <ApplicantAppliedAt<T>>::insert(&applicant, <system::Module<T>>::block_number());
         }
 
         <ApplicantStakes<T>>::insert(applicant.clone(), total_stake);
@@ -636,6 +993,34 @@ impl<T: Trait> Module<T> {
 
         Ok(())
     }
+
+    /// Unseals a vote committed to a ranking of applicants and stores the ranking.
+    fn try_reveal_ranked_vote(voter: T::AccountId, commitment: T::Hash, ranking: Vec<T::AccountId>, salt: Vec<u8>) -> Result {
+        ensure!(<Votes<T>>::exists(&commitment), "commitment not found");
+
+        let mut sealed_vote = <Votes<T>>::get(&commitment);
+
+        ensure!(sealed_vote.is_not_revealed(), "vote already revealed");
+        // only voter can reveal their own votes
+        ensure!(sealed_vote.is_owned_by(voter), "only voter can reveal vote");
+        ensure!(ranking.len() <= Self::candidacy_limit_usize(), "ranking too long");
+
+        for (i, applicant) in ranking.iter().enumerate() {
+            ensure!(<ApplicantStakes<T>>::exists(applicant), "vote for non-applicant not allowed");
+            ensure!(!ranking[..i].contains(applicant), "applicant ranked more than once");
+        }
+
+        let mut salt = salt.clone();
+
+        // Tries to unseal, if salt is invalid will return error
+        sealed_vote.unseal_ranked(&ranking, &mut salt, <T as system::Trait>::Hashing::hash)?;
+
+        // Update the revealed vote
+        <Votes<T>>::insert(commitment, sealed_vote);
+        <RankedVotes<T>>::insert(commitment, ranking);
+
+        Ok(())
+    }
 }
 
 decl_module! {
@@ -672,6 +1057,24 @@ decl_module! {
             Self::deposit_event(RawEvent::Applied(sender));
         }
 
+        /// Applicant can withdraw candidacy during announcing stage only. New stake is returned to free balance
+        /// and transferred stake can be used again when applying or voting.
+        fn withdraw_candidacy(origin) {
+            let sender = ensure_signed(origin)?;
+
+            match Self::stage() {
+                Some(ElectionStage::Announcing(_)) => {},
+                Some(_) => return Err("election not in announcing stage"),
+                None => return Err("election not running"),
+            }
+
+            ensure!(<ApplicantStakes<T>>::exists(&sender), "only applicants can withdraw candidacy");
+
+            Self::drop_applicants(&[sender.clone()]);
+
+            Self::deposit_event(RawEvent::ApplicantWithdrew(sender));
+        }
+
         fn vote(origin, commitment: T::Hash, stake: BalanceOf<T>) {
             let sender = ensure_signed(origin)?;
             ensure!(Self::is_member(sender.clone()), "Only members can vote for an applicant");
@@ -708,62 +1111,109 @@ decl_module! {
             Self::deposit_event(RawEvent::Revealed(sender, commitment, vote));
         }
 
-        fn set_stage_announcing(ends_at: T::BlockNumber) {
+        /// Reveal a vote committed to a list of applicants in order of preference. If a preferred
+        /// applicant is not elected, stake of the vote counts for the next applicant in the list.
+        fn reveal_ranked(origin, commitment: T::Hash, ranking: Vec<T::AccountId>, salt: Vec<u8>) {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(salt.len() <= 32, "salt too large"); // at most 256 bits salt
+
+            match Self::stage() {
+                Some(ElectionStage::Revealing(_)) => {},
+                Some(_) => return Err("election not in revealing stage"),
+                None => return Err("election not running"),
+            }
+
+            Self::try_reveal_ranked_vote(sender.clone(), commitment, ranking.clone(), salt)?;
+            Self::deposit_event(RawEvent::RankedVoteRevealed(sender, commitment, ranking));
+        }
+
+        fn set_stage_announcing(origin, ends_at: T::BlockNumber) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(ends_at > <system::Module<T>>::block_number(), "must end at future block number");
             <Stage<T>>::put(ElectionStage::Announcing(ends_at));
         }
 
-        fn set_stage_revealing(ends_at: T::BlockNumber) {
+        fn set_stage_revealing(origin, ends_at: T::BlockNumber) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(ends_at > <system::Module<T>>::block_number(), "must end at future block number");
             <Stage<T>>::put(ElectionStage::Revealing(ends_at));
         }
 
-        fn set_stage_voting(ends_at: T::BlockNumber) {
+        fn set_stage_voting(origin, ends_at: T::BlockNumber) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(ends_at > <system::Module<T>>::block_number(), "must end at future block number");
             <Stage<T>>::put(ElectionStage::Voting(ends_at));
         }
 
-        fn set_param_announcing_period(period: T::BlockNumber) {
+        fn set_param_announcing_period(origin, period: T::BlockNumber) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(!Self::is_election_running(), "cannot change params during election");
             ensure!(!period.is_zero(), "period cannot be zero");
             <AnnouncingPeriod<T>>::put(period);
         }
-        fn set_param_voting_period(period: T::BlockNumber) {
+        fn set_param_voting_period(origin, period: T::BlockNumber) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(!Self::is_election_running(), "cannot change params during election");
             ensure!(!period.is_zero(), "period cannot be zero");
             <VotingPeriod<T>>::put(period);
         }
-        fn set_param_revealing_period(period: T::BlockNumber) {
+        fn set_param_revealing_period(origin, period: T::BlockNumber) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(!Self::is_election_running(), "cannot change params during election");
             ensure!(!period.is_zero(), "period cannot be zero");
             <RevealingPeriod<T>>::put(period);
         }
-        fn set_param_min_council_stake(amount: BalanceOf<T>) {
+        fn set_param_min_council_stake(origin, amount: BalanceOf<T>) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(!Self::is_election_running(), "cannot change params during election");
             <MinCouncilStake<T>>::put(amount);
         }
-        fn set_param_new_term_duration(duration: T::BlockNumber) {
+        fn set_param_new_term_duration(origin, duration: T::BlockNumber) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(!Self::is_election_running(), "cannot change params during election");
             ensure!(!duration.is_zero(), "new term duration cannot be zero");
             <NewTermDuration<T>>::put(duration);
         }
-        fn set_param_council_size(council_size: u32) {
+        fn set_param_council_size(origin, council_size: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(!Self::is_election_running(), "cannot change params during election");
             ensure!(council_size > 0, "council size cannot be zero");
             ensure!(council_size <= Self::candidacy_limit(), "council size cannot greater than candidacy limit");
             <CouncilSize<T>>::put(council_size);
         }
-        fn set_param_candidacy_limit(limit: u32) {
+        fn set_param_candidacy_limit(origin, limit: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(!Self::is_election_running(), "cannot change params during election");
             ensure!(limit >= Self::council_size(), "candidacy limit cannot be less than council size");
             <CandidacyLimit<T>>::put(limit);
         }
-        fn set_param_min_voting_stake(amount: BalanceOf<T>) {
+        fn set_param_min_voting_stake(origin, amount: BalanceOf<T>) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(!Self::is_election_running(), "cannot change params during election");
             <MinVotingStake<T>>::put(amount);
         }
+        /// Both percents are set together, so the losing vote slash can be checked against the new unrevealed one.
+        fn set_param_vote_slash_percents(origin, unrevealed_percent: u32, losing_percent: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(!Self::is_election_running(), "cannot change params during election");
+            ensure!(unrevealed_percent <= 100, "slash percent cannot be greater than 100");
+            ensure!(losing_percent <= unrevealed_percent, "losing vote slash cannot be greater than unrevealed vote slash");
+            <UnrevealedVoteSlashPercent<T>>::put(unrevealed_percent);
+            <LosingVoteSlashPercent<T>>::put(losing_percent);
+        }
+        fn set_param_seat_selection(origin, method: SeatSelection) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(!Self::is_election_running(), "cannot change params during election");
+            <SeatSelectionMethod<T>>::put(method);
+        }
+        fn set_slashed_stake_recipient(origin, recipient: Option<T::AccountId>) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            <SlashedStakeRecipient<T>>::put(recipient);
+        }
 
-        fn force_stop_election() {
+        fn force_stop_election(origin) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(Self::is_election_running(), "only running election can be stopped");
 
             let mut votes = Vec::new();
@@ -777,15 +1227,18 @@ decl_module! {
             Self::teardown_election (
                 &votes,
                 &empty_council,
-                false /* do not unlock transferable stakes */
+                false, /* do not unlock transferable stakes */
+                false /* voters are not to blame for a stopped election */
             );
         }
 
-        fn force_start_election() {
+        fn force_start_election(origin) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             Self::start_election(<council::Module<T>>::active_council())?;
         }
 
-        fn set_auto_start (flag: bool) {
+        fn set_auto_start(origin, flag: bool) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             <AutoStart<T>>::put(flag);
         }
 
@@ -1002,6 +1455,48 @@ mod tests {
         });
     }
 
+    #[test]
+    fn non_member_cannot_apply_or_vote() {
+        with_externalities(&mut initial_test_ext(), || {
+            Balances::set_free_balance(&NOT_MEMBER, 1000);
+
+            assert_eq!(Election::apply(Origin::signed(NOT_MEMBER), 100),
+                Err("Only members can apply to be on council"));
+            assert_eq!(Election::vote(Origin::signed(NOT_MEMBER), H256::default(), 100),
+                Err("Only members can vote for an applicant"));
+        });
+    }
+
+    #[test]
+    fn withdrawing_candidacy_should_work () {
+        with_externalities(&mut initial_test_ext(), || {
+            let applicant = 20 as u64;
+            Balances::set_free_balance(&applicant, 1000);
+
+            <ExistingStakeHolders<Test>>::put(vec![applicant]);
+            save_transferable_stake(applicant, TransferableStake {seat: 50, backing: 0});
+
+            assert_ok!(Election::start_election(vec![]));
+            assert_err!(Election::withdraw_candidacy(Origin::signed(applicant)), "only applicants can withdraw candidacy");
+
+            assert_ok!(Election::apply(Origin::signed(applicant), 150));
+            assert_eq!(Balances::free_balance(&applicant), 900);
+            assert_eq!(Election::transferable_stakes(applicant).seat, 0);
+
+            assert_ok!(Election::withdraw_candidacy(Origin::signed(applicant)));
+
+            assert!(Election::applicants().is_empty());
+            assert!(!<ApplicantStakes<Test>>::exists(applicant));
+            assert_eq!(Balances::free_balance(&applicant), 1000);
+            assert_eq!(Election::transferable_stakes(applicant).seat, 50);
+
+            // withdrawing is not possible once voting has started
+            assert_ok!(Election::apply(Origin::signed(applicant), 150));
+            Election::move_to_voting_stage();
+            assert_err!(Election::withdraw_candidacy(Origin::signed(applicant)), "election not in announcing stage");
+        });
+    }
+
     #[test]
     fn increasing_applicant_stake_should_work () {
         with_externalities(&mut initial_test_ext(), || {
@@ -1274,6 +1769,143 @@ mod tests {
         <Test as system::Trait>::Hashing::hash(&payload[..])
     }
 
+    fn make_commitment_for_ranking(ranking: &Vec<u64>, salt: &mut Vec<u8>) -> <Test as system::Trait>::Hash {
+        let mut payload = ranking.encode();
+        payload.append(salt);
+        <Test as system::Trait>::Hashing::hash(&payload[..])
+    }
+
+    #[test]
+    fn revealing_ranked_vote_works () {
+        with_externalities(&mut initial_test_ext(), || {
+            let ranking = vec![20, 30];
+            let salt = vec![128u8];
+            let commitment = make_commitment_for_ranking(&ranking, &mut salt.clone());
+            let voter = 5 as u64;
+
+            <ApplicantStakes<Test>>::insert(20, Stake {new: 0, transferred: 0});
+            <ApplicantStakes<Test>>::insert(30, Stake {new: 0, transferred: 0});
+            <Votes<Test>>::insert(&commitment, SealedVote::new(voter, Stake {
+                new: 100, transferred: 0
+            }, commitment));
+
+            assert_err!(Election::try_reveal_ranked_vote(voter, commitment, vec![20, 40], salt.clone()),
+                "vote for non-applicant not allowed");
+            assert_err!(Election::try_reveal_ranked_vote(voter, commitment, vec![20, 20], salt.clone()),
+                "applicant ranked more than once");
+            assert_err!(Election::try_reveal_ranked_vote(voter, commitment, vec![30, 20], salt.clone()),
+                "invalid salt");
+
+            assert_ok!(Election::try_reveal_ranked_vote(voter, commitment, ranking.clone(), salt.clone()));
+            assert_eq!(Election::votes(&commitment).get_vote(), &Some(20));
+            assert_eq!(Election::ranked_votes(&commitment), ranking);
+        });
+    }
+
+    #[test]
+    fn ranked_votes_move_to_next_preference_when_applicant_is_eliminated () {
+        with_externalities(&mut initial_test_ext(), || {
+            <CouncilSize<Test>>::put(2);
+            <Applicants<Test>>::put(vec![100, 200, 300, 400]);
+            for applicant in vec![100, 200, 300, 400] {
+                <ApplicantStakes<Test>>::insert(applicant, Stake {new: 100, transferred: 0});
+            }
+
+            let votes = mock_votes(vec![
+            //  (voter, stake[new], stake[transferred], applicant)
+                (20, 250, 0, 100),
+                (30, 90, 0, 200),
+                (40, 100, 0, 400),
+                (10, 60, 0, 300),
+            ]);
+            for voter in vec![10, 20, 30, 40] {
+                Balances::set_reserved_balance(&voter, 1000);
+            }
+            for (i, vote) in votes.into_iter().enumerate() {
+                let commitment = make_commitment_for_applicant(i as u64, &mut vec![]);
+                <Commitments<Test>>::mutate(|commitments| commitments.push(commitment));
+                <Votes<Test>>::insert(commitment, SealedVote::new_unsealed(vote.voter, vote.stake, commitment, *vote.get_vote().as_ref().unwrap()));
+                if vote.voter == 10 {
+                    <RankedVotes<Test>>::insert(commitment, vec![300, 200]);
+                }
+            }
+
+            Election::on_revealing_ended();
+
+            // 300 is eliminated first and its backing moves to 200, which then beats 400
+            let council = Council::active_council();
+            assert_eq!(council.iter().map(|seat| seat.member).collect::<Vec<_>>(), vec![100, 200]);
+            assert_eq!(council[1].backers, vec![
+                Backer { member: 30, stake: 90 },
+                Backer { member: 10, stake: 60 },
+            ]);
+            assert_eq!(Election::ranked_votes(&make_commitment_for_applicant(3, &mut vec![])), vec![]);
+
+            // stake of the ranked vote backs the council and is not refunded
+            assert_eq!(Balances::reserved_balance(&10), 1000);
+            assert_eq!(Balances::reserved_balance(&40), 900);
+        });
+    }
+
+    fn setup_concentrated_ranked_votes() {
+        <CouncilSize<Test>>::put(2);
+        <Applicants<Test>>::put(vec![100, 200, 300]);
+        for applicant in vec![100, 200, 300] {
+            <ApplicantStakes<Test>>::insert(applicant, Stake {new: 10, transferred: 0});
+        }
+
+        let votes = mock_votes(vec![
+        //  (voter, stake[new], stake[transferred], applicant)
+            (10, 400, 0, 100),
+            (20, 400, 0, 100),
+            (30, 300, 0, 300),
+        ]);
+        for (i, vote) in votes.into_iter().enumerate() {
+            let commitment = make_commitment_for_applicant(i as u64, &mut vec![]);
+            <Commitments<Test>>::mutate(|commitments| commitments.push(commitment));
+            <Votes<Test>>::insert(commitment, SealedVote::new_unsealed(vote.voter, vote.stake, commitment, *vote.get_vote().as_ref().unwrap()));
+            if vote.voter != 30 {
+                <RankedVotes<Test>>::insert(commitment, vec![100, 200]);
+            }
+        }
+    }
+
+    #[test]
+    fn top_staked_seat_selection_concentrates_backing () {
+        with_externalities(&mut initial_test_ext(), || {
+            setup_concentrated_ranked_votes();
+
+            Election::on_revealing_ended();
+
+            let council = Council::active_council();
+            assert_eq!(council.iter().map(|seat| seat.member).collect::<Vec<_>>(), vec![100, 300]);
+            assert_eq!(council[0].calc_total_stake(), 810);
+        });
+    }
+
+    #[test]
+    fn phragmen_seat_selection_balances_backing () {
+        with_externalities(&mut initial_test_ext(), || {
+            assert_ok!(Election::set_param_seat_selection(Origin::ROOT, SeatSelection::Phragmen));
+            setup_concentrated_ranked_votes();
+
+            Election::on_revealing_ended();
+
+            // stake of voters ranking 100 and 200 is enough for two seats, and is split between them
+            let council = Council::active_council();
+            assert_eq!(council.iter().map(|seat| seat.member).collect::<Vec<_>>(), vec![100, 200]);
+            assert_eq!(council[0].stake, 10);
+            assert_eq!(council[0].backers, vec![
+                Backer { member: 10, stake: 201 },
+                Backer { member: 20, stake: 201 },
+            ]);
+            assert_eq!(council[1].backers, vec![
+                Backer { member: 10, stake: 199 },
+                Backer { member: 20, stake: 199 },
+            ]);
+        });
+    }
+
     #[test]
     fn revealing_vote_works () {
         with_externalities(&mut initial_test_ext(), || {
@@ -1391,7 +2023,7 @@ mod tests {
                 (30, 400, 0, 300),
             ]);
 
-            let tally = Election::tally_votes(&votes);
+            let tally = Election::tally_votes(&votes, &[]);
 
             assert_eq!(tally.len(), 3);
 
@@ -1436,7 +2068,7 @@ mod tests {
    #[test]
     fn filter_top_staked_applicants_should_work () {
         with_externalities(&mut initial_test_ext(), || {
-            // filter_top_staked depends on order of applicants
+            // filter_top_staked only keeps seats of applicants
             <Applicants<Test>>::put(vec![100, 200, 300]);
 
             {
@@ -1452,7 +2084,7 @@ mod tests {
                     (30, 400, 0, 300),
                 ]);
 
-                let mut tally = Election::tally_votes(&votes);
+                let mut tally = Election::tally_votes(&votes, &[]);
                 assert_eq!(tally.len(), 3);
                 Election::filter_top_staked(&mut tally, 3);
                 assert_eq!(tally.len(), 3);
@@ -1471,7 +2103,7 @@ mod tests {
                     (30, 400, 0, 300),
                 ]);
 
-                let mut tally = Election::tally_votes(&votes);
+                let mut tally = Election::tally_votes(&votes, &[]);
                 assert_eq!(tally.len(), 3);
                 Election::filter_top_staked(&mut tally, 2);
                 assert_eq!(tally.len(), 2);
@@ -1481,6 +2113,121 @@ mod tests {
         });
     }
 
+    fn keep_top_seat(applicants: Vec<u64>, seats: Vec<Seat<u64, u32>>) -> Vec<u64> {
+        <Applicants<Test>>::put(applicants);
+        let mut tally: BTreeMap<u64, Seat<u64, u32>> = seats.into_iter()
+            .map(|seat| (seat.member, seat))
+            .collect();
+        Election::filter_top_staked(&mut tally, 1);
+        tally.keys().cloned().collect()
+    }
+
+    #[test]
+    fn filter_top_staked_breaks_ties_by_backer_count () {
+        with_externalities(&mut initial_test_ext(), || {
+            let seats = vec![
+                Seat { member: 100, stake: 100, backers: vec![Backer { member: 10, stake: 200 }] },
+                Seat { member: 200, stake: 100, backers: vec![Backer { member: 10, stake: 100 }, Backer { member: 20, stake: 100 }] },
+            ];
+            // applicant 100 applied earlier, but 200 has more backers for the same total stake
+            <ApplicantAppliedAt<Test>>::insert(100, 1);
+            <ApplicantAppliedAt<Test>>::insert(200, 2);
+
+            assert_eq!(keep_top_seat(vec![100, 200], seats.clone()), vec![200]);
+            assert_eq!(keep_top_seat(vec![200, 100], seats), vec![200]);
+        });
+    }
+
+    #[test]
+    fn filter_top_staked_breaks_ties_by_application_block () {
+        with_externalities(&mut initial_test_ext(), || {
+            Balances::set_free_balance(&100, 1000);
+            Balances::set_free_balance(&200, 1000);
+
+            System::set_block_number(3);
+            assert_ok!(Election::try_add_applicant(100, 50));
+            System::set_block_number(5);
+            assert_ok!(Election::try_add_applicant(200, 100));
+            System::set_block_number(7);
+            assert_ok!(Election::try_add_applicant(100, 50));
+
+            // increasing stake does not change when the applicant applied
+            assert_eq!(Election::applicant_applied_at(100), 3);
+            assert_eq!(Election::applicant_applied_at(200), 5);
+
+            let seats = vec![
+                Seat { member: 100, stake: 100, backers: vec![] },
+                Seat { member: 200, stake: 100, backers: vec![] },
+            ];
+            assert_eq!(keep_top_seat(vec![100, 200], seats.clone()), vec![100]);
+            assert_eq!(keep_top_seat(vec![200, 100], seats), vec![100]);
+
+            Election::drop_applicants(&[100]);
+            assert!(!<ApplicantAppliedAt<Test>>::exists(100));
+        });
+    }
+
+    #[test]
+    fn phragmen_elects_balanced_set_with_large_stakes () {
+        let unit: u128 = 1_000_000_000_000_000_000;
+        let voter = |who: u64, stake: u128, approvals: Vec<u64>| PhragmenVoter {
+            who, stake, budget: 0, load: 0, approvals, edges: vec![], is_applicant: false,
+        };
+        let mut voters = vec![
+            voter(10, 40 * unit, vec![1, 2]),
+            voter(11, 30 * unit, vec![3]),
+            voter(12, unit, vec![1]),
+        ];
+        let divisor = phragmen_divisor(voters.iter().fold(0, |total, voter| total + voter.stake));
+        for voter in voters.iter_mut() {
+            voter.budget = voter.stake / divisor;
+        }
+
+        // Greater accounts win ties, so scores that lost their precision would elect 3 and 2.
+        let elected = phragmen_elect(&[1, 2, 3], &mut voters, 2, |who, _| *who);
+        assert_eq!(elected, vec![1, 3]);
+    }
+
+    #[test]
+    fn phragmen_breaks_ties_by_application_block () {
+        with_externalities(&mut initial_test_ext(), || {
+            for applicant in vec![100, 200] {
+                <ApplicantStakes<Test>>::insert(applicant, Stake {new: 100, transferred: 0});
+            }
+            <ApplicantAppliedAt<Test>>::insert(100, 5);
+            <ApplicantAppliedAt<Test>>::insert(200, 3);
+
+            // equal scores, so the earlier applicant wins regardless of order of applicants
+            <Applicants<Test>>::put(vec![100, 200]);
+            let tally = Election::phragmen_seats(&vec![], 1);
+            assert_eq!(tally.keys().cloned().collect::<Vec<_>>(), vec![200]);
+
+            <Applicants<Test>>::put(vec![200, 100]);
+            let tally = Election::phragmen_seats(&vec![], 1);
+            assert_eq!(tally.keys().cloned().collect::<Vec<_>>(), vec![200]);
+        });
+    }
+
+    #[test]
+    fn filter_top_staked_breaks_remaining_ties_by_random_seed () {
+        with_externalities(&mut initial_test_ext(), || {
+            let seats = vec![
+                Seat { member: 100, stake: 100, backers: vec![Backer { member: 10, stake: 100 }] },
+                Seat { member: 200, stake: 100, backers: vec![Backer { member: 20, stake: 100 }] },
+            ];
+
+            let random_seed = System::random_seed();
+            let expected = if BlakeTwo256::hash_of(&(random_seed, 100u64)) > BlakeTwo256::hash_of(&(random_seed, 200u64)) {
+                100
+            } else {
+                200
+            };
+
+            assert_eq!(keep_top_seat(vec![100, 200], seats.clone()), vec![expected]);
+            assert_eq!(keep_top_seat(vec![200, 100], seats), vec![expected]);
+        });
+    }
+
     #[test]
     fn drop_unelected_applicants_should_work () {
         with_externalities(&mut initial_test_ext(), || {
@@ -1544,7 +2291,7 @@ mod tests {
             new_council.insert(200 as u64, Seat{ member: 200 as u64, stake: 0 as u32, backers: vec![]});
             new_council.insert(300 as u64, Seat{ member: 300 as u64, stake: 0 as u32, backers: vec![]});
 
-            Election::refund_voting_stakes(&votes, &new_council);
+            Election::refund_voting_stakes(&votes, &new_council, true);
 
             assert_eq!(Balances::free_balance(&10), 1100); assert_eq!(Balances::reserved_balance(&10), 4900);
             assert_eq!(Balances::free_balance(&20), 2200); assert_eq!(Balances::reserved_balance(&20), 4800);
@@ -1556,6 +2303,73 @@ mod tests {
         });
     }
 
+    #[test]
+    fn unrevealed_and_losing_votes_should_be_slashed () {
+        with_externalities(&mut initial_test_ext(), || {
+            Balances::set_free_balance(&10, 1000); Balances::set_reserved_balance(&10, 5000);
+            Balances::set_free_balance(&20, 2000); Balances::set_reserved_balance(&20, 5000);
+            save_transferable_stake(10, TransferableStake {seat: 0, backing: 100});
+            save_transferable_stake(20, TransferableStake {seat: 0, backing: 200});
+
+            assert_err!(Election::set_param_vote_slash_percents(Origin::ROOT, 5, 10),
+                "losing vote slash cannot be greater than unrevealed vote slash");
+            assert_err!(Election::set_param_vote_slash_percents(Origin::ROOT, 101, 10),
+                "slash percent cannot be greater than 100");
+            assert_ok!(Election::set_param_vote_slash_percents(Origin::ROOT, 50, 10));
+
+            let recipient = 99;
+            Balances::set_free_balance(&recipient, 1);
+            assert_ok!(Election::set_slashed_stake_recipient(Origin::ROOT, Some(recipient)));
+
+            let mut votes = mock_votes(vec![
+            //  (voter, stake[new], stake[transferred], applicant)
+                (10, 100, 0, 100),
+                (20, 100, 100, 200),
+            ]);
+            // vote of 10 was never revealed
+            votes[0] = SealedVote::new(10, votes[0].stake, votes[0].commitment);
+
+            let mut new_council: BTreeMap<u64, Seat<u64, u32>> = BTreeMap::new();
+            new_council.insert(300 as u64, Seat{ member: 300 as u64, stake: 0 as u32, backers: vec![]});
+
+            Election::refund_voting_stakes(&votes, &new_council, true);
+
+            // 50% of 100 slashed
+            assert_eq!(Balances::free_balance(&10), 1050); assert_eq!(Balances::reserved_balance(&10), 4900);
+            assert_eq!(Election::transferable_stakes(10).backing, 100);
+
+            // 10% of 200 slashed from new stake
+            assert_eq!(Balances::free_balance(&20), 2080); assert_eq!(Balances::reserved_balance(&20), 4900);
+            assert_eq!(Election::transferable_stakes(20).backing, 300);
+
+            assert_eq!(Balances::free_balance(&recipient), 71);
+        });
+    }
+
+    #[test]
+    fn slashed_voting_stake_should_be_burned_without_recipient () {
+        with_externalities(&mut initial_test_ext(), || {
+            Balances::set_free_balance(&10, 1000); Balances::set_reserved_balance(&10, 5000);
+            save_transferable_stake(10, TransferableStake {seat: 0, backing: 100});
+
+            assert_ok!(Election::set_param_vote_slash_percents(Origin::ROOT, 30, 30));
+
+            // transferred stake is slashed once new stake is used up
+            let votes = mock_votes(vec![(10, 20, 80, 200)]);
+            let new_council: BTreeMap<u64, Seat<u64, u32>> = BTreeMap::new();
+
+            Election::refund_voting_stakes(&votes, &new_council, true);
+
+            assert_eq!(Balances::free_balance(&10), 1000); assert_eq!(Balances::reserved_balance(&10), 4970);
+            assert_eq!(Election::transferable_stakes(10).backing, 170);
+
+            // no slashing when the election was stopped
+            Election::refund_voting_stakes(&votes, &new_council, false);
+            assert_eq!(Balances::free_balance(&10), 1020);
+            assert_eq!(Election::transferable_stakes(10).backing, 250);
+        });
+    }
+
     #[test]
     fn unlock_transferable_stakes_should_work () {
        with_externalities(&mut initial_test_ext(), || {
@@ -1,7 +1,8 @@
 #![cfg(test)]
 
 use rstd::prelude::*;
-pub use super::{election, council, proposals, GovernanceCurrency};
+pub use super::{election, council, proposals, motions, GovernanceCurrency};
+pub use crate::traits::Members;
 pub use system;
 
 pub use primitives::{H256, Blake2Hasher};
@@ -11,10 +12,19 @@ pub use runtime_primitives::{
     testing::{Digest, DigestItem, Header, UintAuthorityId}
 };
 
-use srml_support::impl_outer_origin;
+use srml_support::{impl_outer_origin, impl_outer_dispatch};
 
 impl_outer_origin! {
-    pub enum Origin for Test {}
+    pub enum Origin for Test {
+        council
+    }
+}
+
+impl_outer_dispatch! {
+    pub enum Call for Test where origin: Origin {
+        council::Council,
+        election::Election,
+    }
 }
 
 // For testing the module, we construct most of a mock runtime. This means
@@ -48,14 +58,25 @@ impl council::Trait for Test {
     type Event = ();
 
     type CouncilTermEnded = (Election,);
+
+    type CouncilOrigin = council::RootOrCouncilMajority<Test>;
 }
 impl election::Trait for Test {
     type Event = ();
 
     type CouncilElected = (Council,);
+
+    type Members = MockMembership;
+}
+impl motions::Trait for Test {
+    type Origin = Origin;
+    type Proposal = Call;
+    type Event = ();
 }
 impl proposals::Trait for Test {
     type Event = ();
+    type OnProposalSlash = ();
+    type Members = MockMembership;
 }
 impl balances::Trait for Test {
     type Event = ();
@@ -80,6 +101,16 @@ impl GovernanceCurrency for Test {
     type Currency = balances::Module<Self>;
 }
 
+pub const NOT_MEMBER: u64 = 999;
+
+/// Every account except `NOT_MEMBER` is a member.
+pub struct MockMembership;
+impl Members<Test> for MockMembership {
+    fn is_active_member(account: &u64) -> bool {
+        *account != NOT_MEMBER
+    }
+}
+
 // TODO add a Hook type to capture TriggerElection and CouncilElected hooks
 
 // This function basically just builds a genesis storage key/value store according to
@@ -93,5 +124,6 @@ pub fn initial_test_ext() -> runtime_io::TestExternalities<Blake2Hasher> {
 pub type Election = election::Module<Test>;
 pub type Council = council::Module<Test>;
 pub type Proposals = proposals::Module<Test>;
+pub type Motions = motions::Module<Test>;
 pub type System = system::Module<Test>;
 pub type Balances = balances::Module<Test>;
@@ -6,9 +6,11 @@ use system;
 pub mod election;
 pub mod council;
 pub mod proposals;
+pub mod motions;
 
 mod stake;
 mod sealed_vote;
+mod runtime_code;
 
 pub trait GovernanceCurrency: system::Trait + Sized {
     type Currency: ArithmeticType + Currency<<Self as system::Trait>::AccountId, Balance=BalanceOf<Self>>;
@@ -0,0 +1,416 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use srml_support::{StorageValue, StorageMap, dispatch::Result, decl_module, decl_event, decl_storage, ensure};
+use srml_support::dispatch::{Dispatchable, Parameter};
+use runtime_primitives::traits::{As, Hash};
+use system::{self, ensure_signed};
+use rstd::prelude::*;
+
+use super::council::{self, EnsureCouncilOrigin};
+
+const DEFAULT_VOTING_PERIOD: u64 = 14_400;
+const DEFAULT_MAX_ACTIVE_MOTIONS: u32 = 10;
+
+const MSG_ONLY_COUNCILORS_CAN_PROPOSE: &str = "Only councilors can propose a motion";
+const MSG_ONLY_COUNCILORS_CAN_VOTE: &str = "Only councilors can vote on a motion";
+const MSG_INVALID_THRESHOLD: &str = "Threshold must be greater than zero and not greater than council size";
+const MSG_DUPLICATE_MOTION: &str = "The same motion has been proposed already";
+const MSG_MOTION_NOT_FOUND: &str = "There is no motion with such hash";
+const MSG_WRONG_MOTION_INDEX: &str = "Motion index does not match the motion hash";
+const MSG_DUPLICATE_VOTE: &str = "You have already voted in the same way on this motion";
+const MSG_TOO_MANY_MOTIONS: &str = "Too many motions are open for voting";
+
+pub type MotionIndex = u32;
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+/// Votes of councilors on a motion.
+pub struct MotionVotes<AccountId, BlockNumber> {
+    pub index: MotionIndex,
+    /// Number of ayes required to execute the motion.
+    pub threshold: u32,
+    pub ayes: Vec<AccountId>,
+    pub nays: Vec<AccountId>,
+    /// Block at which the motion expires if it has not been approved.
+    pub end: BlockNumber,
+}
+
+pub trait Trait: council::Trait {
+    /// The outer origin type. A motion is dispatched with a council origin.
+    type Origin: From<council::RawOrigin>;
+
+    /// The outer call type that a motion is made of.
+    type Proposal: Parameter + Dispatchable<Origin=<Self as Trait>::Origin>;
+
+    type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as CouncilMotions {
+        /// Hashes of motions that are open for voting.
+        ActiveMotions get(active_motions): Vec<T::Hash>;
+
+        MotionByHash get(motion_by_hash): map T::Hash => Option<<T as Trait>::Proposal>;
+
+        VotesByMotion get(votes_by_motion): map T::Hash => Option<MotionVotes<T::AccountId, T::BlockNumber>>;
+
+        /// Count of all motions that have been proposed.
+        MotionCount get(motion_count): u32;
+
+        /// Number of blocks a motion is open for voting.
+        VotingPeriod get(voting_period): T::BlockNumber = T::BlockNumber::sa(DEFAULT_VOTING_PERIOD);
+
+        /// Max number of motions that can be open for voting at the same time.
+        MaxActiveMotions get(max_active_motions): u32 = DEFAULT_MAX_ACTIVE_MOTIONS;
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        <T as system::Trait>::Hash,
+        <T as system::Trait>::AccountId
+    {
+        /// Params:
+        /// * Councilor who proposed a motion.
+        /// * Index of the motion.
+        /// * Hash of the motion.
+        /// * Number of ayes required to execute the motion.
+        Proposed(AccountId, MotionIndex, Hash, u32),
+
+        /// Params:
+        /// * Voter.
+        /// * Hash of a motion.
+        /// * Whether the vote is aye.
+        /// * Number of ayes and nays so far.
+        Voted(AccountId, Hash, bool, u32, u32),
+
+        Approved(Hash),
+        Disapproved(Hash),
+
+        /// A motion was not approved before the end of its voting period.
+        Expired(Hash),
+
+        /// A motion was dispatched. The flag tells whether the dispatch was successful.
+        Executed(Hash, bool),
+    }
+);
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: <T as system::Trait>::Origin {
+
+        fn deposit_event<T>() = default;
+
+        /// Propose a call to be dispatched once a given number of councilors approve it.
+        /// The proposer votes aye on the motion.
+        fn propose(origin, threshold: u32, motion: Box<<T as Trait>::Proposal>) {
+            let proposer = ensure_signed(origin)?;
+            ensure!(Self::is_councilor(&proposer), MSG_ONLY_COUNCILORS_CAN_PROPOSE);
+            ensure!(threshold > 0 && threshold <= Self::council_size(), MSG_INVALID_THRESHOLD);
+
+            let motion_hash = T::Hashing::hash_of(&motion);
+            ensure!(!<MotionByHash<T>>::exists(motion_hash), MSG_DUPLICATE_MOTION);
+            ensure!(threshold == 1 || (Self::active_motions().len() as u32) < Self::max_active_motions(),
+                MSG_TOO_MANY_MOTIONS);
+
+            let index = Self::motion_count();
+            <MotionCount<T>>::put(index + 1);
+            Self::deposit_event(RawEvent::Proposed(proposer.clone(), index, motion_hash, threshold));
+
+            if threshold == 1 {
+                Self::_execute_motion(motion_hash, *motion, 1);
+            } else {
+                <ActiveMotions<T>>::mutate(|hashes| hashes.push(motion_hash));
+                <MotionByHash<T>>::insert(motion_hash, *motion);
+                <VotesByMotion<T>>::insert(motion_hash, MotionVotes {
+                    index,
+                    threshold,
+                    ayes: vec![proposer],
+                    nays: vec![],
+                    end: <system::Module<T>>::block_number() + Self::voting_period(),
+                });
+            }
+        }
+
+        /// Vote on a motion. A councilor can change a vote by voting again.
+        /// Votes of accounts that are no longer councilors are dropped and do not count.
+        fn vote(origin, motion_hash: T::Hash, index: MotionIndex, approve: bool) {
+            let voter = ensure_signed(origin)?;
+            ensure!(Self::is_councilor(&voter), MSG_ONLY_COUNCILORS_CAN_VOTE);
+
+            let mut votes = Self::votes_by_motion(motion_hash).ok_or(MSG_MOTION_NOT_FOUND)?;
+            ensure!(votes.index == index, MSG_WRONG_MOTION_INDEX);
+
+            let (same_side, other_side) = if approve {
+                (&mut votes.ayes, &mut votes.nays)
+            } else {
+                (&mut votes.nays, &mut votes.ayes)
+            };
+            ensure!(!same_side.contains(&voter), MSG_DUPLICATE_VOTE);
+            other_side.retain(|account| *account != voter);
+            same_side.push(voter.clone());
+
+            // The council could have changed since the motion was proposed.
+            votes.ayes.retain(|account| Self::is_councilor(account));
+            votes.nays.retain(|account| Self::is_councilor(account));
+
+            let ayes = votes.ayes.len() as u32;
+            let nays = votes.nays.len() as u32;
+            Self::deposit_event(RawEvent::Voted(voter, motion_hash, approve, ayes, nays));
+
+            let council_size = Self::council_size();
+            if ayes >= votes.threshold {
+                Self::deposit_event(RawEvent::Approved(motion_hash));
+                if let Some(motion) = Self::_remove_motion(motion_hash) {
+                    Self::_execute_motion(motion_hash, motion, ayes);
+                }
+            } else if council_size.saturating_sub(nays) < votes.threshold {
+                // Not enough councilors left to approve this motion.
+                Self::deposit_event(RawEvent::Disapproved(motion_hash));
+                Self::_remove_motion(motion_hash);
+            } else {
+                <VotesByMotion<T>>::insert(motion_hash, votes);
+            }
+        }
+
+        fn on_finalise(now: T::BlockNumber) {
+            Self::_close_stale_motions(now);
+        }
+
+        fn set_voting_period(origin, new_value: T::BlockNumber) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(new_value > T::BlockNumber::sa(0), "voting period must be greater than zero");
+            <VotingPeriod<T>>::put(new_value);
+        }
+
+        fn set_max_active_motions(origin, new_value: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            <MaxActiveMotions<T>>::put(new_value);
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+
+    fn is_councilor(sender: &T::AccountId) -> bool {
+        <council::Module<T>>::is_councilor(sender)
+    }
+
+    fn council_size() -> u32 {
+        <council::Module<T>>::active_council().len() as u32
+    }
+
+    fn _remove_motion(motion_hash: T::Hash) -> Option<<T as Trait>::Proposal> {
+        <ActiveMotions<T>>::mutate(|hashes| hashes.retain(|hash| *hash != motion_hash));
+        <VotesByMotion<T>>::remove(motion_hash);
+        <MotionByHash<T>>::take(motion_hash)
+    }
+
+    /// Close motions whose voting period is over and motions that need more ayes
+    /// than there are councilors, e.g. after the council has been replaced by a smaller one.
+    fn _close_stale_motions(now: T::BlockNumber) {
+        let council_size = Self::council_size();
+        for motion_hash in Self::active_motions() {
+            let votes = match Self::votes_by_motion(motion_hash) {
+                Some(votes) => votes,
+                None => continue,
+            };
+            if now >= votes.end {
+                Self::deposit_event(RawEvent::Expired(motion_hash));
+                Self::_remove_motion(motion_hash);
+            } else if votes.threshold > council_size {
+                Self::deposit_event(RawEvent::Disapproved(motion_hash));
+                Self::_remove_motion(motion_hash);
+            }
+        }
+    }
+
+    fn _execute_motion(motion_hash: T::Hash, motion: <T as Trait>::Proposal, approvals: u32) {
+        let origin = council::RawOrigin::Members(approvals, Self::council_size());
+        let ok = motion.dispatch(origin.into()).is_ok();
+        Self::deposit_event(RawEvent::Executed(motion_hash, ok));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::governance::mock::*;
+    use runtime_io::with_externalities;
+    use srml_support::*;
+
+    fn set_council_motion(accounts: Vec<u64>) -> Box<Call> {
+        Box::new(Call::Council(council::Call::set_council(accounts)))
+    }
+
+    fn motion_hash(motion: &Box<Call>) -> H256 {
+        BlakeTwo256::hash_of(motion)
+    }
+
+    fn setup_council() {
+        assert_ok!(Council::set_council(Origin::ROOT, vec![1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn only_councilors_can_propose_motion() {
+        with_externalities(&mut initial_test_ext(), || {
+            setup_council();
+
+            assert_eq!(Motions::propose(Origin::signed(6), 3, set_council_motion(vec![6])),
+                Err(MSG_ONLY_COUNCILORS_CAN_PROPOSE));
+            assert_eq!(Motions::propose(Origin::signed(1), 0, set_council_motion(vec![6])),
+                Err(MSG_INVALID_THRESHOLD));
+            assert_eq!(Motions::propose(Origin::signed(1), 6, set_council_motion(vec![6])),
+                Err(MSG_INVALID_THRESHOLD));
+
+            let motion = set_council_motion(vec![6]);
+            assert_ok!(Motions::propose(Origin::signed(1), 3, motion.clone()));
+            assert_eq!(Motions::active_motions(), vec![motion_hash(&motion)]);
+            assert_eq!(Motions::propose(Origin::signed(2), 3, motion),
+                Err(MSG_DUPLICATE_MOTION));
+        });
+    }
+
+    #[test]
+    fn approved_motion_is_dispatched_with_council_origin() {
+        with_externalities(&mut initial_test_ext(), || {
+            setup_council();
+
+            let motion = set_council_motion(vec![6, 7]);
+            let hash = motion_hash(&motion);
+            assert_ok!(Motions::propose(Origin::signed(1), 3, motion));
+
+            assert_ok!(Motions::vote(Origin::signed(2), hash, 0, true));
+            assert_eq!(Motions::vote(Origin::signed(2), hash, 0, true), Err(MSG_DUPLICATE_VOTE));
+            assert_eq!(Motions::vote(Origin::signed(3), hash, 1, true), Err(MSG_WRONG_MOTION_INDEX));
+            assert!(Council::is_councilor(&1));
+
+            assert_ok!(Motions::vote(Origin::signed(3), hash, 0, true));
+
+            assert!(Council::is_councilor(&6));
+            assert!(Council::is_councilor(&7));
+            assert!(!Council::is_councilor(&1));
+            assert!(Motions::active_motions().is_empty());
+            assert_eq!(Motions::votes_by_motion(hash), None);
+        });
+    }
+
+    #[test]
+    fn motion_approved_by_council_minority_cannot_make_privileged_call() {
+        with_externalities(&mut initial_test_ext(), || {
+            setup_council();
+
+            let motion = set_council_motion(vec![6]);
+            let hash = motion_hash(&motion);
+            assert_ok!(Motions::propose(Origin::signed(1), 2, motion));
+            assert_ok!(Motions::vote(Origin::signed(2), hash, 0, true));
+
+            // 2 of 5 councilors is not a majority:
+            assert!(Motions::active_motions().is_empty());
+            assert!(!Council::is_councilor(&6));
+        });
+    }
+
+    #[test]
+    fn motion_is_disapproved_when_threshold_cannot_be_reached() {
+        with_externalities(&mut initial_test_ext(), || {
+            setup_council();
+
+            let motion = set_council_motion(vec![6]);
+            let hash = motion_hash(&motion);
+            assert_ok!(Motions::propose(Origin::signed(1), 4, motion));
+
+            assert_ok!(Motions::vote(Origin::signed(2), hash, 0, false));
+            assert_eq!(Motions::votes_by_motion(hash).unwrap().nays, vec![2]);
+
+            // A councilor can change a vote:
+            assert_ok!(Motions::vote(Origin::signed(2), hash, 0, true));
+            assert_eq!(Motions::votes_by_motion(hash).unwrap().ayes, vec![1, 2]);
+
+            assert_ok!(Motions::vote(Origin::signed(3), hash, 0, false));
+            assert_ok!(Motions::vote(Origin::signed(4), hash, 0, false));
+
+            assert!(Motions::active_motions().is_empty());
+            assert_eq!(Motions::motion_by_hash(hash), None);
+            assert!(!Council::is_councilor(&6));
+        });
+    }
+
+    #[test]
+    fn motion_expires_at_end_of_voting_period() {
+        with_externalities(&mut initial_test_ext(), || {
+            setup_council();
+            System::set_block_number(1);
+            assert_ok!(Motions::set_voting_period(Origin::ROOT, 10));
+
+            let motion = set_council_motion(vec![6]);
+            let hash = motion_hash(&motion);
+            assert_ok!(Motions::propose(Origin::signed(1), 3, motion));
+            assert_eq!(Motions::votes_by_motion(hash).unwrap().end, 11);
+
+            Motions::on_finalise(10);
+            assert_eq!(Motions::active_motions(), vec![hash]);
+
+            Motions::on_finalise(11);
+            assert!(Motions::active_motions().is_empty());
+            assert_eq!(Motions::votes_by_motion(hash), None);
+            assert_eq!(Motions::vote(Origin::signed(2), hash, 0, true), Err(MSG_MOTION_NOT_FOUND));
+        });
+    }
+
+    #[test]
+    fn motion_is_closed_when_council_is_smaller_than_threshold() {
+        with_externalities(&mut initial_test_ext(), || {
+            setup_council();
+
+            let motion = set_council_motion(vec![6]);
+            let hash = motion_hash(&motion);
+            assert_ok!(Motions::propose(Origin::signed(1), 4, motion));
+
+            assert_ok!(Council::set_council(Origin::ROOT, vec![1, 2, 3]));
+            Motions::on_finalise(1);
+
+            assert!(Motions::active_motions().is_empty());
+            assert_eq!(Motions::motion_by_hash(hash), None);
+        });
+    }
+
+    #[test]
+    fn number_of_active_motions_is_limited() {
+        with_externalities(&mut initial_test_ext(), || {
+            setup_council();
+            assert_ok!(Motions::set_max_active_motions(Origin::ROOT, 1));
+
+            assert_ok!(Motions::propose(Origin::signed(1), 3, set_council_motion(vec![6])));
+            assert_eq!(Motions::propose(Origin::signed(1), 3, set_council_motion(vec![7])),
+                Err(MSG_TOO_MANY_MOTIONS));
+        });
+    }
+
+    #[test]
+    fn votes_of_former_councilors_do_not_count() {
+        with_externalities(&mut initial_test_ext(), || {
+            setup_council();
+
+            let motion = set_council_motion(vec![8]);
+            let hash = motion_hash(&motion);
+            assert_ok!(Motions::propose(Origin::signed(1), 3, motion));
+            assert_ok!(Motions::vote(Origin::signed(2), hash, 0, true));
+
+            assert_ok!(Council::set_council(Origin::ROOT, vec![3, 4, 5, 6, 7]));
+
+            // Ayes of 1 and 2 are dropped, so the motion is not approved yet:
+            assert_ok!(Motions::vote(Origin::signed(3), hash, 0, true));
+            assert_eq!(Motions::votes_by_motion(hash).unwrap().ayes, vec![3]);
+            assert_eq!(Motions::active_motions(), vec![hash]);
+            assert!(!Council::is_councilor(&8));
+
+            assert_ok!(Motions::vote(Origin::signed(4), hash, 0, true));
+            assert_ok!(Motions::vote(Origin::signed(5), hash, 0, true));
+
+            assert!(Motions::active_motions().is_empty());
+            assert!(Council::is_councilor(&8));
+            assert!(!Council::is_councilor(&3));
+        });
+    }
+}
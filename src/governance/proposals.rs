@@ -1,25 +1,42 @@
 use srml_support::{StorageValue, StorageMap, dispatch::Result, decl_module, decl_event, decl_storage, ensure};
 use srml_support::traits::{Currency};
 use primitives::{storage::well_known_keys};
-use runtime_primitives::traits::{As, Hash, Zero};
+use runtime_primitives::traits::{As, Hash, Zero, SimpleArithmetic};
 use runtime_io::print;
 use {balances, system::{self, ensure_signed}};
 use rstd::prelude::*;
+use rstd::collections::btree_set::BTreeSet;
+
+use super::council::{self, EnsureCouncilOrigin};
+use super::runtime_code::{self, SpecVersion};
+use crate::traits::Members;
 
-use super::council;
 pub use super::{ GovernanceCurrency, BalanceOf };
 
 const DEFAULT_APPROVAL_QUORUM: u32 = 60;
+const DEFAULT_APPROVAL_THRESHOLD: u32 = 0;
 const DEFAULT_MIN_STAKE: u64 = 100;
 const DEFAULT_CANCELLATION_FEE: u64 = 5;
 const DEFAULT_REJECTION_FEE: u64 = 10;
+const DEFAULT_DEPOSIT_PER_BYTE: u64 = 1;
 
 const DEFAULT_VOTING_PERIOD_IN_DAYS: u64 = 10;
 const DEFAULT_VOTING_PERIOD_IN_SECS: u64 = DEFAULT_VOTING_PERIOD_IN_DAYS * 24 * 60 * 60;
 
+const DEFAULT_GRACE_PERIOD_IN_HOURS: u64 = 24;
+const DEFAULT_GRACE_PERIOD_IN_SECS: u64 = DEFAULT_GRACE_PERIOD_IN_HOURS * 60 * 60;
+
+const DEFAULT_RETENTION_PERIOD_IN_DAYS: u64 = 30;
+const DEFAULT_RETENTION_PERIOD_IN_SECS: u64 = DEFAULT_RETENTION_PERIOD_IN_DAYS * 24 * 60 * 60;
+
 const DEFAULT_NAME_MAX_LEN: u32 = 100;
 const DEFAULT_DESCRIPTION_MAX_LEN: u32 = 10_000;
 const DEFAULT_WASM_CODE_MAX_LEN: u32 = 2_000_000;
+const DEFAULT_POST_MAX_LEN: u32 = 2_000;
+const DEFAULT_MAX_POSTS_PER_PROPOSAL: u32 = 100;
+const DEFAULT_MAX_DELEGATION_DEPTH: u32 = 3;
+const DEFAULT_MAX_FOLLOWERS_PER_DELEGATE: u32 = 20;
+const DEFAULT_MAX_ADVISORY_VOTES_PER_PROPOSAL: u32 = 500;
 
 const MSG_STAKE_IS_TOO_LOW: &str = "Stake is too low";
 const MSG_STAKE_IS_GREATER_THAN_BALANCE: &str = "Balance is too low to be staked";
@@ -29,6 +46,7 @@ const MSG_PROPOSAL_NOT_FOUND: &str = "This proposal does not exist";
 const MSG_PROPOSAL_EXPIRED: &str = "Voting period is expired for this proposal";
 const MSG_PROPOSAL_FINALIZED: &str = "Proposal is finalized already";
 const MSG_YOU_ALREADY_VOTED: &str = "You have already voted on this proposal";
+const MSG_YOU_DID_NOT_VOTE: &str = "You have not voted on this proposal";
 const MSG_YOU_DONT_OWN_THIS_PROPOSAL: &str = "You do not own this proposal";
 const MSG_PROPOSAL_STATUS_ALREADY_UPDATED: &str = "Proposal status has been updated already";
 const MSG_EMPTY_NAME_PROVIDED: &str = "Proposal cannot have an empty name";
@@ -37,6 +55,30 @@ const MSG_EMPTY_WASM_CODE_PROVIDED: &str = "Proposal cannot have an empty WASM c
 const MSG_TOO_LONG_NAME: &str = "Name is too long";
 const MSG_TOO_LONG_DESCRIPTION: &str = "Description is too long";
 const MSG_TOO_LONG_WASM_CODE: &str = "WASM code is too big";
+const MSG_WASM_CODE_NOT_FOUND: &str = "There is no WASM code with such hash";
+const MSG_WASM_CODE_IS_IN_USE: &str = "WASM code is referenced by active or pending proposals";
+const MSG_INVALID_APPROVAL_QUORUM: &str = "Approval quorum must be greater than zero and not greater than 100";
+const MSG_INVALID_APPROVAL_THRESHOLD: &str = "Approval threshold cannot be greater than 100";
+const MSG_ZERO_VOTING_PERIOD: &str = "Voting period cannot be zero";
+const MSG_FEE_IS_GREATER_THAN_MIN_STAKE: &str = "Fee cannot be greater than minimum stake";
+const MSG_ZERO_TREASURY_SPEND: &str = "Treasury spend amount cannot be zero";
+const MSG_TREASURY_BALANCE_TOO_LOW: &str = "Treasury balance is too low to cover the spend";
+const MSG_ACCOUNT_IS_COUNCILOR: &str = "Account is a councilor already";
+const MSG_ACCOUNT_IS_NOT_COUNCILOR: &str = "Account is not a councilor";
+const MSG_ONLY_MEMBERS_CAN_POST: &str = "Only members and councilors can post in a proposal discussion";
+const MSG_EMPTY_POST_PROVIDED: &str = "Discussion post cannot be empty";
+const MSG_TOO_LONG_POST: &str = "Discussion post is too long";
+const MSG_TOO_MANY_POSTS: &str = "Proposal discussion has reached the max number of posts";
+const MSG_DESCRIPTION_ALREADY_AMENDED: &str = "Proposal description can be amended only once";
+const MSG_ONLY_MEMBERS_CAN_SIGNAL: &str = "Only members can cast or delegate an advisory vote";
+const MSG_COUNCILORS_CANNOT_SIGNAL: &str = "Councilors cannot cast or delegate an advisory vote";
+const MSG_CANNOT_DELEGATE_TO_SELF: &str = "Advisory vote cannot be delegated to yourself";
+const MSG_DELEGATE_IS_NOT_MEMBER: &str = "Advisory vote can be delegated only to a member or a councilor";
+const MSG_DELEGATION_CYCLE: &str = "Delegation would create a cycle";
+const MSG_DELEGATION_TOO_DEEP: &str = "Delegation chain would be longer than allowed";
+const MSG_NO_DELEGATION: &str = "Account has not delegated its advisory vote";
+const MSG_TOO_MANY_FOLLOWERS: &str = "Delegate has reached the max number of followers";
+const MSG_TOO_MANY_ADVISORY_VOTES: &str = "Proposal has reached the max number of advisory votes";
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
 #[derive(Encode, Decode, Clone, PartialEq, Eq)]
@@ -49,11 +91,19 @@ pub enum ProposalStatus {
     Expired,
     /// To clear the quorum requirement, the percentage of council members with revealed votes
     /// must be no less than the quorum value for the given proposal type.
+    /// A proposal gets this status once it has been executed after the grace period.
     Approved,
     Rejected,
     /// If all revealed votes are slashes, then the proposal is rejected,
     /// and the proposal stake is slashed.
     Slashed,
+    /// Approved by the council and waiting for the grace period to pass before it is executed.
+    PendingExecution,
+    /// Approved by the council, but its execution returned an error.
+    ExecutionFailed,
+    /// Replaced by a revised proposal of the same proposer.
+    /// The stake has been moved to the new proposal.
+    Superseded,
 }
 
 impl Default for ProposalStatus {
@@ -88,33 +138,306 @@ use self::VoteKind::*;
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
 #[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
-/// Proposal for node runtime update.
-pub struct RuntimeUpgradeProposal<AccountId, Balance, BlockNumber, Hash> {
+/// Number of votes of every kind cast on a proposal so far.
+pub struct VoteCounts {
+    pub abstentions: u32,
+    pub approvals: u32,
+    pub rejections: u32,
+    pub slashes: u32,
+}
+
+impl VoteCounts {
+    fn counter_mut(&mut self, vote: &VoteKind) -> &mut u32 {
+        match vote {
+            Abstain => &mut self.abstentions,
+            Approve => &mut self.approvals,
+            Reject => &mut self.rejections,
+            Slash => &mut self.slashes,
+        }
+    }
+
+    pub fn add(&mut self, vote: &VoteKind) {
+        *self.counter_mut(vote) += 1;
+    }
+
+    pub fn remove(&mut self, vote: &VoteKind) {
+        let counter = self.counter_mut(vote);
+        *counter = counter.saturating_sub(1);
+    }
+
+    pub fn total(&self) -> u32 {
+        self.abstentions + self.approvals + self.rejections + self.slashes
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+/// Community sentiment on a proposal: total stake of members behind every kind of vote.
+/// It is advisory and does not affect the outcome of a proposal.
+pub struct AdvisoryTally<Balance> {
+    pub abstentions: Balance,
+    pub approvals: Balance,
+    pub rejections: Balance,
+    pub slashes: Balance,
+    /// Number of members who voted directly or through a delegate.
+    pub voters: u32,
+}
+
+impl<Balance: SimpleArithmetic + Copy> AdvisoryTally<Balance> {
+    pub fn add(&mut self, vote: &VoteKind, stake: Balance) {
+        let total = match vote {
+            Abstain => &mut self.abstentions,
+            Approve => &mut self.approvals,
+            Reject => &mut self.rejections,
+            Slash => &mut self.slashes,
+        };
+        *total = total.saturating_add(stake);
+        self.voters += 1;
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+/// A parameter of this module that can be changed by a proposal.
+pub enum ProposalParameter<Balance, BlockNumber> {
+    ApprovalQuorum(u32),
+    MinStake(Balance),
+    CancellationFee(Balance),
+    RejectionFee(Balance),
+    VotingPeriod(BlockNumber),
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+/// What a proposal is about and what will be executed once it is approved.
+pub enum ProposalKind<AccountId, Balance, BlockNumber, Hash> {
+    /// Update of node's runtime to WASM code with a given hash.
+    RuntimeUpgrade(Hash),
+    /// A text-only signal of the council. Nothing is executed on approval.
+    Text,
+    /// Set a new value of a module parameter.
+    ParameterChange(ProposalParameter<Balance, BlockNumber>),
+    /// Transfer some balance from the treasury account to a given account.
+    TreasurySpend(AccountId, Balance),
+    /// Add a new zero staked councilor to the active council.
+    AddCouncilMember(AccountId),
+    /// Remove a councilor from the active council.
+    RemoveCouncilMember(AccountId),
+}
+
+impl<AccountId, Balance, BlockNumber, Hash> Default for ProposalKind<AccountId, Balance, BlockNumber, Hash> {
+    fn default() -> Self {
+        ProposalKind::Text
+    }
+}
+
+impl<AccountId, Balance, BlockNumber, Hash> ProposalKind<AccountId, Balance, BlockNumber, Hash> {
+    pub fn category(&self) -> ProposalCategory {
+        match self {
+            ProposalKind::RuntimeUpgrade(_) => ProposalCategory::RuntimeUpgrade,
+            ProposalKind::Text => ProposalCategory::Text,
+            ProposalKind::ParameterChange(_) => ProposalCategory::ParameterChange,
+            ProposalKind::TreasurySpend(_, _) => ProposalCategory::TreasurySpend,
+            ProposalKind::AddCouncilMember(_) |
+            ProposalKind::RemoveCouncilMember(_) => ProposalCategory::CouncilMembership,
+        }
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+/// Proposal kinds that share the same voting rules.
+pub enum ProposalCategory {
+    RuntimeUpgrade,
+    Text,
+    ParameterChange,
+    TreasurySpend,
+    CouncilMembership,
+}
+
+impl Default for ProposalCategory {
+    fn default() -> Self {
+        ProposalCategory::Text
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+/// Voting rules of a proposal category.
+pub struct ProposalSettings<Balance, BlockNumber> {
+    /// A percent (up to 100) of the council participants
+    /// that must vote affirmatively in order to pass.
+    pub quorum_percent: u32,
+    /// A percent (up to 100) of approvals among all approvals, rejections and slashes
+    /// that is required in order to pass.
+    pub approval_threshold_percent: u32,
+    /// Max duration of proposal in blocks until it will be expired if not enough votes.
+    pub voting_period: BlockNumber,
+    /// Minimum amount of a balance to be staked in order to make a proposal.
+    pub min_stake: Balance,
+    /// How votes of councilors are counted.
+    pub tally_mode: TallyMode,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+pub enum TallyMode {
+    /// Every councilor has one vote.
+    Count,
+    /// A vote of a councilor is weighted by the total stake of the councilor's seat,
+    /// i.e. the councilor's own stake and stakes of the backers.
+    /// Quorum and approval threshold are percents of stake rather than of councilors.
+    StakeWeighted,
+}
+
+impl Default for TallyMode {
+    fn default() -> Self {
+        TallyMode::Count
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+/// Total stake of council seats behind every kind of vote on a proposal.
+pub struct VoteStakes<Balance> {
+    pub abstentions: Balance,
+    pub approvals: Balance,
+    pub rejections: Balance,
+    pub slashes: Balance,
+    /// Total stake of all seats of the council, including councilors who did not vote.
+    pub council_stake: Balance,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+pub struct Proposal<AccountId, Balance, BlockNumber, Hash> {
     id: u32,
     proposer: AccountId,
     stake: Balance,
+    /// Storage deposit paid for the size of name, description and uploaded WASM code.
+    deposit: Balance,
     name: Vec<u8>,
     description: Vec<u8>,
-    wasm_hash: Hash,
+    kind: ProposalKind<AccountId, Balance, BlockNumber, Hash>,
     proposed_at: BlockNumber,
+    /// Block at which the voting period of this proposal ends.
+    /// It is fixed on creation, so later changes of the voting period do not affect it.
+    expires_at: BlockNumber,
     status: ProposalStatus,
+    /// Id of a revised proposal that replaced this one.
+    superseded_by: Option<u32>,
 }
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
 #[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
-pub struct TallyResult<BlockNumber> {
+pub struct TallyResult<Balance, BlockNumber> {
     proposal_id: u32,
     abstentions: u32,
     approvals: u32,
     rejections: u32,
     slashes: u32,
+    /// Weighted totals if the proposal has been tallied in the stake weighted mode.
+    weighted: Option<VoteStakes<Balance>>,
+    status: ProposalStatus,
+    finalized_at: BlockNumber,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+/// A comment in a discussion of a proposal.
+pub struct DiscussionPost<AccountId, BlockNumber> {
+    author: AccountId,
+    text: Vec<u8>,
+    posted_at: BlockNumber,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+/// What is kept of a finalized proposal after its details and votes are pruned.
+pub struct ProposalSummary<AccountId, BlockNumber> {
+    id: u32,
+    proposer: AccountId,
     status: ProposalStatus,
+    votes: VoteCounts,
     finalized_at: BlockNumber,
 }
 
+/// Hook for handling a balance that has been slashed from a proposer.
+pub trait OnProposalSlash<T: Trait> {
+    /// Take an amount from a reserved balance of a proposer.
+    /// Returns the accounts that received the slashed balance and the amounts they received.
+    /// A part of the amount that has no recipient is burned.
+    fn on_proposal_slash(proposal_id: u32, proposer: &T::AccountId, amount: BalanceOf<T>)
+        -> Vec<(T::AccountId, BalanceOf<T>)>;
+}
+
+/// Burns a slashed balance.
+impl<T: Trait> OnProposalSlash<T> for () {
+    fn on_proposal_slash(_proposal_id: u32, proposer: &T::AccountId, amount: BalanceOf<T>)
+        -> Vec<(T::AccountId, BalanceOf<T>)>
+    {
+        let _ = T::Currency::slash_reserved(proposer, amount);
+        vec![]
+    }
+}
+
+/// Moves a slashed balance to the treasury account of the proposals module.
+pub struct SlashToTreasury;
+
+impl<T: Trait> OnProposalSlash<T> for SlashToTreasury {
+    fn on_proposal_slash(proposal_id: u32, proposer: &T::AccountId, amount: BalanceOf<T>)
+        -> Vec<(T::AccountId, BalanceOf<T>)>
+    {
+        let treasury = <Module<T>>::treasury_account();
+        match T::Currency::repatriate_reserved(proposer, &treasury, amount) {
+            Ok(_) => vec![(treasury, amount)],
+            // Treasury account does not exist, so there is nobody to receive the balance:
+            Err(_) => <() as OnProposalSlash<T>>::on_proposal_slash(proposal_id, proposer, amount),
+        }
+    }
+}
+
+/// Splits a slashed balance equally among councilors that voted on the proposal.
+/// A remainder of the split is burned.
+pub struct SlashToVoters;
+
+impl<T: Trait> OnProposalSlash<T> for SlashToVoters {
+    fn on_proposal_slash(proposal_id: u32, proposer: &T::AccountId, amount: BalanceOf<T>)
+        -> Vec<(T::AccountId, BalanceOf<T>)>
+    {
+        let voters: Vec<T::AccountId> = <Module<T>>::votes_by_proposal(proposal_id)
+            .into_iter()
+            .map(|(voter, _)| voter)
+            .filter(|voter| voter != proposer)
+            .collect();
+
+        let mut recipients = vec![];
+        let mut paid = BalanceOf::<T>::zero();
+        if !voters.is_empty() {
+            let share = amount / BalanceOf::<T>::sa(voters.len() as u64);
+            if !share.is_zero() {
+                for voter in voters {
+                    if T::Currency::repatriate_reserved(proposer, &voter, share).is_ok() {
+                        paid = paid + share;
+                        recipients.push((voter, share));
+                    }
+                }
+            }
+        }
+
+        let _ = T::Currency::slash_reserved(proposer, amount - paid);
+        recipients
+    }
+}
+
 pub trait Trait: timestamp::Trait + council::Trait + GovernanceCurrency {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+    /// Decides where stakes and fees slashed from proposers go.
+    type OnProposalSlash: OnProposalSlash<Self>;
+
+    type Members: Members<Self>;
 }
 
 decl_event!(
@@ -122,7 +445,8 @@ decl_event!(
     where
         <T as system::Trait>::Hash,
         <T as system::Trait>::BlockNumber,
-        <T as system::Trait>::AccountId
+        <T as system::Trait>::AccountId,
+        Balance = BalanceOf<T>
     {
         // New events
 
@@ -139,13 +463,81 @@ decl_event!(
         /// * Kind of vote.
         Voted(AccountId, u32, VoteKind),
 
-        TallyFinalized(TallyResult<BlockNumber>),
+        /// Params:
+        /// * Voter - an account id of a councilor.
+        /// * Id of a proposal.
+        /// * New kind of vote.
+        VoteChanged(AccountId, u32, VoteKind),
+        VoteRetracted(AccountId, u32),
+
+        TallyFinalized(TallyResult<Balance, BlockNumber>),
 
         /// * Hash - hash of wasm code of runtime update.
         RuntimeUpdated(u32, Hash),
 
+        /// WASM code with a given hash is not referenced by proposals anymore
+        /// and has been removed from storage.
+        WasmCodeRemoved(Hash),
+
         /// Root cancelled proposal
         ProposalVetoed(u32),
+
+        /// Params:
+        /// * Id of a replaced proposal.
+        /// * Id of a revised proposal.
+        ProposalSuperseded(u32, u32),
+
+        /// Params:
+        /// * Id of a proposal.
+        /// * Amount slashed from a proposer.
+        /// * Accounts that received the slashed amount. The rest of the amount has been burned.
+        ProposerSlashed(u32, Balance, Vec<(AccountId, Balance)>),
+
+        /// Params:
+        /// * Id of an approved proposal.
+        /// * Block number at which the proposal will be executed.
+        ProposalScheduled(u32, BlockNumber),
+        ProposalExecuted(u32),
+
+        /// Params:
+        /// * Id of an approved proposal.
+        /// * Error message returned by the execution.
+        ProposalExecutionFailed(u32, Vec<u8>),
+
+        /// A module parameter has been changed by an approved proposal.
+        ParameterUpdated(u32),
+
+        /// Params:
+        /// * Id of an approved proposal.
+        /// * Account that received the spend.
+        /// * Amount transferred from the treasury account.
+        TreasurySpent(u32, AccountId, Balance),
+
+        CouncilMemberAdded(u32, AccountId),
+        CouncilMemberRemoved(u32, AccountId),
+
+        /// Params:
+        /// * Author of a post.
+        /// * Id of a discussed proposal.
+        /// * Index of a new post in the discussion.
+        DiscussionPostAdded(AccountId, u32, u32),
+
+        /// Proposer replaced the description of a proposal.
+        DescriptionAmended(u32),
+
+        /// Details and votes of a finalized proposal have been removed from storage.
+        /// Its summary is still available.
+        ProposalPruned(u32),
+
+        /// A member cast or changed an advisory vote on a proposal.
+        AdvisoryVoted(AccountId, u32, VoteKind),
+
+        /// Params:
+        /// * Member who delegated an advisory vote.
+        /// * Delegate: a councilor or another member.
+        SignalDelegated(AccountId, AccountId),
+
+        SignalUndelegated(AccountId),
     }
 );
 
@@ -159,6 +551,10 @@ decl_storage! {
         /// that must vote affirmatively in order to pass.
         ApprovalQuorum get(approval_quorum) config(): u32 = DEFAULT_APPROVAL_QUORUM;
 
+        /// A percent (up to 100) of approvals among all approvals, rejections and slashes
+        /// that is required in order to pass. Zero means that only the quorum is checked.
+        ApprovalThreshold get(approval_threshold) config(): u32 = DEFAULT_APPROVAL_THRESHOLD;
+
         /// Minimum amount of a balance to be staked in order to make a proposal.
         MinStake get(min_stake) config(): BalanceOf<T> =
             BalanceOf::<T>::sa(DEFAULT_MIN_STAKE);
@@ -171,14 +567,51 @@ decl_storage! {
         RejectionFee get(rejection_fee) config(): BalanceOf<T> =
             BalanceOf::<T>::sa(DEFAULT_REJECTION_FEE);
 
+        /// A deposit to be reserved per every byte of name, description and WASM code
+        /// of a new proposal. It is returned to a proposer unless the proposal is slashed.
+        DepositPerByte get(deposit_per_byte) config(): BalanceOf<T> =
+            BalanceOf::<T>::sa(DEFAULT_DEPOSIT_PER_BYTE);
+
         /// Max duration of proposal in blocks until it will be expired if not enough votes.
         VotingPeriod get(voting_period) config(): T::BlockNumber =
             T::BlockNumber::sa(DEFAULT_VOTING_PERIOD_IN_SECS /
             <timestamp::Module<T>>::block_period().as_());
 
+        /// Number of blocks between approval of a proposal and its execution.
+        /// It gives node operators a warning before a runtime upgrade is applied.
+        GracePeriod get(grace_period) config(): T::BlockNumber =
+            T::BlockNumber::sa(DEFAULT_GRACE_PERIOD_IN_SECS /
+            <timestamp::Module<T>>::block_period().as_());
+
+        /// Number of blocks that details and votes of a finalized proposal are kept for.
+        RetentionPeriod get(retention_period) config(): T::BlockNumber =
+            T::BlockNumber::sa(DEFAULT_RETENTION_PERIOD_IN_SECS /
+            <timestamp::Module<T>>::block_period().as_());
+
         NameMaxLen get(name_max_len) config(): u32 = DEFAULT_NAME_MAX_LEN;
         DescriptionMaxLen get(description_max_len) config(): u32 = DEFAULT_DESCRIPTION_MAX_LEN;
         WasmCodeMaxLen get(wasm_code_max_len) config(): u32 = DEFAULT_WASM_CODE_MAX_LEN;
+        PostMaxLen get(post_max_len) config(): u32 = DEFAULT_POST_MAX_LEN;
+        MaxPostsPerProposal get(max_posts_per_proposal) config(): u32 = DEFAULT_MAX_POSTS_PER_PROPOSAL;
+
+        /// Max number of delegations between a member and a voter whose advisory vote the member follows.
+        MaxDelegationDepth get(max_delegation_depth) config(): u32 = DEFAULT_MAX_DELEGATION_DEPTH;
+
+        /// Max number of members who can follow advisory votes of the same account,
+        /// directly or through other delegates. Together with the max number of advisory votes
+        /// it bounds the work of an advisory tally.
+        MaxFollowersPerDelegate get(max_followers_per_delegate) config(): u32 = DEFAULT_MAX_FOLLOWERS_PER_DELEGATE;
+
+        /// Max number of members who can cast advisory votes on a proposal directly.
+        MaxAdvisoryVotesPerProposal get(max_advisory_votes_per_proposal) config(): u32 =
+            DEFAULT_MAX_ADVISORY_VOTES_PER_PROPOSAL;
+
+        /// Voting rules that override the parameters above for proposals of a given category.
+        SettingsByCategory get(settings_by_category) config():
+            map ProposalCategory => ProposalSettings<BalanceOf<T>, T::BlockNumber>;
+
+        /// Account that pays for approved treasury spend proposals.
+        TreasuryAccount get(treasury_account) config(): T::AccountId;
 
         // Persistent state (always relevant, changes constantly):
 
@@ -186,7 +619,7 @@ decl_storage! {
         ProposalCount get(proposal_count): u32;
 
         /// Get proposal details by its id.
-        Proposals get(proposals): map u32 => RuntimeUpgradeProposal<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>;
+        Proposals get(proposals): map u32 => Proposal<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>;
 
         /// Ids of proposals that are open for voting (have not been finalized yet).
         ActiveProposalIds get(active_proposal_ids): Vec<u32> = vec![];
@@ -194,12 +627,83 @@ decl_storage! {
         /// Get WASM code of runtime upgrade by hash of its content.
         WasmCodeByHash get(wasm_code_by_hash): map T::Hash => Vec<u8>;
 
+        /// Number of active or pending proposals that refer to WASM code with a given hash.
+        /// The code is removed once no proposal refers to it.
+        WasmCodeRefCount get(wasm_code_ref_count): map T::Hash => u32;
+
         VotesByProposal get(votes_by_proposal): map u32 => Vec<(T::AccountId, VoteKind)>;
 
+        /// Vote counters of a proposal. They are updated on every vote,
+        /// so a tally does not need to re-count all votes of a proposal.
+        VoteCountsByProposal get(vote_counts_by_proposal): map u32 => VoteCounts;
+
+        /// Ids of active proposals by a block number at which their voting period ends.
+        ProposalIdsByExpiry get(proposal_ids_by_expiry): map T::BlockNumber => Vec<u32>;
+
+        /// Ids of active proposals that received votes since the last tally.
+        ProposalIdsToTally get(proposal_ids_to_tally): Vec<u32> = vec![];
+
         // TODO Rethink: this can be replaced with: votes_by_proposal.find(|vote| vote.0 == proposer)
         VoteByAccountAndProposal get(vote_by_account_and_proposal): map (T::AccountId, u32) => VoteKind;
 
-        TallyResults get(tally_results): map u32 => TallyResult<T::BlockNumber>;
+        TallyResults get(tally_results): map u32 => TallyResult<BalanceOf<T>, T::BlockNumber>;
+
+        /// Ids of approved proposals by a block number at which they will be executed.
+        ScheduledExecutions get(scheduled_executions): map T::BlockNumber => Vec<u32>;
+
+        /// Block number at which an approved proposal will be executed.
+        ExecutesAt get(executes_at): map u32 => T::BlockNumber;
+
+        /// Summaries of finalized proposals. They are kept after proposals are pruned.
+        ProposalSummaries get(proposal_summaries): map u32 => ProposalSummary<T::AccountId, T::BlockNumber>;
+
+        /// Ids of finalized proposals by a block number at which they will be pruned.
+        ProposalIdsToPrune get(proposal_ids_to_prune): map T::BlockNumber => Vec<u32>;
+
+        /// Number of proposals created by a given account.
+        ProposalCountByProposer get(proposal_count_by_proposer): map T::AccountId => u32;
+
+        /// Id of a proposal by its proposer and its position among proposals of the proposer.
+        /// A position can change when another proposal of the proposer is pruned.
+        ProposalIdByProposer get(proposal_id_by_proposer): map (T::AccountId, u32) => u32;
+
+        /// Position of a proposal among proposals of its proposer.
+        ProposerPositionOfProposal get(proposer_position_of_proposal): map u32 => u32;
+
+        /// Number of proposals that have a given status.
+        ProposalCountByStatus get(proposal_count_by_status): map ProposalStatus => u32;
+
+        /// Id of a proposal by its status and its position among proposals with this status.
+        /// A position can change when another proposal leaves the status.
+        ProposalIdByStatus get(proposal_id_by_status): map (ProposalStatus, u32) => u32;
+
+        /// Position of a proposal among proposals with the same status.
+        StatusPositionOfProposal get(status_position_of_proposal): map u32 => u32;
+
+        /// Discussion of a proposal. Posting is possible while the proposal is active.
+        PostsByProposal get(posts_by_proposal): map u32 => Vec<DiscussionPost<T::AccountId, T::BlockNumber>>;
+
+        /// Descriptions of a proposal that have been replaced by an amendment.
+        DescriptionHistory get(description_history): map u32 => Vec<Vec<u8>>;
+
+        /// Advisory votes cast by members directly.
+        AdvisoryVotesByProposal get(advisory_votes_by_proposal): map u32 => Vec<(T::AccountId, VoteKind)>;
+
+        /// A councilor or a member whose vote is followed by a given member
+        /// on proposals where the member has not voted directly.
+        SignalDelegateOf get(signal_delegate_of): map T::AccountId => Option<T::AccountId>;
+
+        /// Members who delegated their advisory votes to a given account.
+        SignalDelegators get(signal_delegators): map T::AccountId => Vec<T::AccountId>;
+
+        /// Number of delegations in the longest chain of delegators that ends at a given account.
+        SignalDelegationHeight get(signal_delegation_height): map T::AccountId => u32;
+
+        /// Number of members who follow a given account, directly or through other delegates.
+        SignalFollowerCount get(signal_follower_count): map T::AccountId => u32;
+
+        /// Community sentiment on a proposal at the moment its tally was finalized.
+        AdvisoryTallyResults get(advisory_tally_results): map u32 => AdvisoryTally<BalanceOf<T>>;
     }
 }
 
@@ -219,52 +723,66 @@ decl_module! {
             description: Vec<u8>,
             wasm_code: Vec<u8>
         ) {
-
             let proposer = ensure_signed(origin)?;
-            ensure!(Self::is_member(proposer.clone()), MSG_ONLY_MEMBERS_CAN_PROPOSE);
-            ensure!(stake >= Self::min_stake(), MSG_STAKE_IS_TOO_LOW);
-
-            ensure!(!name.is_empty(), MSG_EMPTY_NAME_PROVIDED);
-            ensure!(name.len() as u32 <= Self::name_max_len(), MSG_TOO_LONG_NAME);
-
-            ensure!(!description.is_empty(), MSG_EMPTY_DESCRIPTION_PROVIDED);
-            ensure!(description.len() as u32 <= Self::description_max_len(), MSG_TOO_LONG_DESCRIPTION);
-
-            ensure!(!wasm_code.is_empty(), MSG_EMPTY_WASM_CODE_PROVIDED);
-            ensure!(wasm_code.len() as u32 <= Self::wasm_code_max_len(), MSG_TOO_LONG_WASM_CODE);
-
-            // Lock proposer's stake:
-            T::Currency::reserve(&proposer, stake)
-                .map_err(|_| MSG_STAKE_IS_GREATER_THAN_BALANCE)?;
-
-            let proposal_id = Self::proposal_count() + 1;
-            <ProposalCount<T>>::put(proposal_id);
 
             // See in substrate repo @ srml/contract/src/wasm/code_cache.rs:73
             let wasm_hash = T::Hashing::hash(&wasm_code);
 
-            let new_proposal = RuntimeUpgradeProposal {
-                id: proposal_id,
-                proposer: proposer.clone(),
+            Self::_create_proposal(
+                proposer,
                 stake,
+                Zero::zero(),
                 name,
                 description,
-                wasm_hash,
-                proposed_at: Self::current_block(),
-                status: Active
-            };
+                ProposalKind::RuntimeUpgrade(wasm_hash),
+                Some(wasm_code)
+            )?;
+        }
 
-            if !<WasmCodeByHash<T>>::exists(wasm_hash) {
-              <WasmCodeByHash<T>>::insert(wasm_hash, wasm_code);
-            }
-            <Proposals<T>>::insert(proposal_id, new_proposal);
-            <ActiveProposalIds<T>>::mutate(|ids| ids.push(proposal_id));
-            Self::deposit_event(RawEvent::ProposalCreated(proposer.clone(), proposal_id));
+        /// Create a proposal of any kind. A runtime upgrade proposal created this way
+        /// must refer to WASM code that has been already uploaded with `create_proposal`.
+        fn create_proposal_of_kind(
+            origin,
+            stake: BalanceOf<T>,
+            name: Vec<u8>,
+            description: Vec<u8>,
+            kind: ProposalKind<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>
+        ) {
+            let proposer = ensure_signed(origin)?;
+            Self::_create_proposal(proposer, stake, Zero::zero(), name, description, kind, None)?;
+        }
 
-            // Auto-vote with Approve if proposer is a councilor:
-            if Self::is_councilor(&proposer) {
-                Self::_process_vote(proposer, proposal_id, Approve)?;
-            }
+        /// Replace an active runtime upgrade proposal with a revised one.
+        /// The stake moves to the new proposal without a cancellation fee, and voting starts over.
+        fn supersede_proposal(
+            origin,
+            proposal_id: u32,
+            name: Vec<u8>,
+            description: Vec<u8>,
+            wasm_code: Vec<u8>
+        ) {
+            let proposer = ensure_signed(origin)?;
+            let wasm_hash = T::Hashing::hash(&wasm_code);
+            Self::_supersede_proposal(
+                proposer,
+                proposal_id,
+                name,
+                description,
+                ProposalKind::RuntimeUpgrade(wasm_hash),
+                Some(wasm_code)
+            )?;
+        }
+
+        /// Replace an active proposal with a revised proposal of any kind.
+        fn supersede_proposal_of_kind(
+            origin,
+            proposal_id: u32,
+            name: Vec<u8>,
+            description: Vec<u8>,
+            kind: ProposalKind<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>
+        ) {
+            let proposer = ensure_signed(origin)?;
+            Self::_supersede_proposal(proposer, proposal_id, name, description, kind, None)?;
         }
 
         /// Use next code to create a proposal from Substrate UI's web console:
@@ -275,18 +793,178 @@ decl_module! {
             let voter = ensure_signed(origin)?;
             ensure!(Self::is_councilor(&voter), MSG_ONLY_COUNCILORS_CAN_VOTE);
 
+            Self::_ensure_voting_is_open(proposal_id)?;
+
+            let did_not_vote_before = !<VoteByAccountAndProposal<T>>::exists((voter.clone(), proposal_id));
+            ensure!(did_not_vote_before, MSG_YOU_ALREADY_VOTED);
+
+            Self::_process_vote(voter, proposal_id, vote)?;
+        }
+
+        /// Change a vote cast before on a proposal that is still open for voting.
+        fn change_vote(origin, proposal_id: u32, vote: VoteKind) {
+            let voter = ensure_signed(origin)?;
+            ensure!(Self::is_councilor(&voter), MSG_ONLY_COUNCILORS_CAN_VOTE);
+
+            Self::_ensure_voting_is_open(proposal_id)?;
+
+            let voted_before = <VoteByAccountAndProposal<T>>::exists((voter.clone(), proposal_id));
+            ensure!(voted_before, MSG_YOU_DID_NOT_VOTE);
+
+            <VotesByProposal<T>>::mutate(proposal_id, |votes| {
+                for (account, old_vote) in votes.iter_mut() {
+                    if *account == voter {
+                        *old_vote = vote.clone();
+                    }
+                }
+            });
+            let old_vote = <VoteByAccountAndProposal<T>>::get((voter.clone(), proposal_id));
+            <VoteCountsByProposal<T>>::mutate(proposal_id, |counts| {
+                counts.remove(&old_vote);
+                counts.add(&vote);
+            });
+            <VoteByAccountAndProposal<T>>::insert((voter.clone(), proposal_id), &vote);
+            Self::_schedule_tally(proposal_id);
+            Self::deposit_event(RawEvent::VoteChanged(voter, proposal_id, vote));
+        }
+
+        /// Retract a vote cast before on a proposal that is still open for voting.
+        fn retract_vote(origin, proposal_id: u32) {
+            let voter = ensure_signed(origin)?;
+            ensure!(Self::is_councilor(&voter), MSG_ONLY_COUNCILORS_CAN_VOTE);
+
+            Self::_ensure_voting_is_open(proposal_id)?;
+
+            let voted_before = <VoteByAccountAndProposal<T>>::exists((voter.clone(), proposal_id));
+            ensure!(voted_before, MSG_YOU_DID_NOT_VOTE);
+
+            <VotesByProposal<T>>::mutate(proposal_id, |votes| votes.retain(|(account, _)| *account != voter));
+            let old_vote = <VoteByAccountAndProposal<T>>::take((voter.clone(), proposal_id));
+            <VoteCountsByProposal<T>>::mutate(proposal_id, |counts| counts.remove(&old_vote));
+            Self::_schedule_tally(proposal_id);
+            Self::deposit_event(RawEvent::VoteRetracted(voter, proposal_id));
+        }
+
+        /// Cast or change an advisory vote on a proposal that is open for voting.
+        /// It overrides a vote of the member's delegate on this proposal.
+        fn cast_advisory_vote(origin, proposal_id: u32, vote: VoteKind) {
+            let voter = ensure_signed(origin)?;
+            Self::_ensure_can_signal(&voter)?;
+            Self::_ensure_voting_is_open(proposal_id)?;
+
+            let mut votes = Self::advisory_votes_by_proposal(proposal_id);
+            let voted_before = votes.iter().any(|(account, _)| *account == voter);
+            ensure!(voted_before || (votes.len() as u32) < Self::max_advisory_votes_per_proposal(),
+                MSG_TOO_MANY_ADVISORY_VOTES);
+
+            votes.retain(|(account, _)| *account != voter);
+            votes.push((voter.clone(), vote.clone()));
+            <AdvisoryVotesByProposal<T>>::insert(proposal_id, votes);
+            Self::deposit_event(RawEvent::AdvisoryVoted(voter, proposal_id, vote));
+        }
+
+        /// Follow advisory votes of a councilor or another member
+        /// on proposals where the sender has not voted directly.
+        fn delegate_signal(origin, delegate: T::AccountId) {
+            let delegator = ensure_signed(origin)?;
+            Self::_ensure_can_signal(&delegator)?;
+
+            ensure!(delegate != delegator, MSG_CANNOT_DELEGATE_TO_SELF);
+            let delegate_can_vote = Self::is_member(delegate.clone()) || Self::is_councilor(&delegate);
+            ensure!(delegate_can_vote, MSG_DELEGATE_IS_NOT_MEMBER);
+
+            // Walk up the chain of the delegate to check its length and that it does not lead back.
+            // Members who delegate to the sender make the chain longer too.
+            let mut depth = 1 + Self::signal_delegation_height(&delegator);
+            let mut next = Self::signal_delegate_of(&delegate);
+            while let Some(account) = next {
+                ensure!(account != delegator, MSG_DELEGATION_CYCLE);
+                depth += 1;
+                next = Self::signal_delegate_of(&account);
+            }
+            ensure!(depth <= Self::max_delegation_depth(), MSG_DELEGATION_TOO_DEEP);
+
+            // The sender and its followers join every account up the new chain,
+            // except those that they already follow through the current delegation.
+            let moved = 1 + Self::signal_follower_count(&delegator);
+            let followed_now = match Self::signal_delegate_of(&delegator) {
+                Some(old_delegate) => Self::_signal_delegation_chain(&old_delegate),
+                None => vec![],
+            };
+            for account in Self::_signal_delegation_chain(&delegate) {
+                ensure!(followed_now.contains(&account) ||
+                    Self::signal_follower_count(&account) + moved <= Self::max_followers_per_delegate(),
+                    MSG_TOO_MANY_FOLLOWERS);
+            }
+
+            Self::_remove_signal_delegation(&delegator);
+            <SignalDelegateOf<T>>::insert(&delegator, &delegate);
+            <SignalDelegators<T>>::mutate(&delegate, |delegators| delegators.push(delegator.clone()));
+            for account in Self::_signal_delegation_chain(&delegate) {
+                <SignalFollowerCount<T>>::mutate(&account, |count| *count += moved);
+            }
+            Self::_update_delegation_heights(&delegate);
+            Self::deposit_event(RawEvent::SignalDelegated(delegator, delegate));
+        }
+
+        fn undelegate_signal(origin) {
+            let delegator = ensure_signed(origin)?;
+            ensure!(<SignalDelegateOf<T>>::exists(&delegator), MSG_NO_DELEGATION);
+
+            Self::_remove_signal_delegation(&delegator);
+            Self::deposit_event(RawEvent::SignalUndelegated(delegator));
+        }
+
+        /// Post a comment in a discussion of an active proposal.
+        fn add_discussion_post(origin, proposal_id: u32, text: Vec<u8>) {
+            let author = ensure_signed(origin)?;
+            let can_post = Self::is_member(author.clone()) || Self::is_councilor(&author);
+            ensure!(can_post, MSG_ONLY_MEMBERS_CAN_POST);
+
+            ensure!(<Proposals<T>>::exists(proposal_id), MSG_PROPOSAL_NOT_FOUND);
+            ensure!(Self::proposals(proposal_id).status == Active, MSG_PROPOSAL_FINALIZED);
+
+            ensure!(!text.is_empty(), MSG_EMPTY_POST_PROVIDED);
+            ensure!(text.len() as u32 <= Self::post_max_len(), MSG_TOO_LONG_POST);
+
+            let post_index = Self::posts_by_proposal(proposal_id).len() as u32;
+            ensure!(post_index < Self::max_posts_per_proposal(), MSG_TOO_MANY_POSTS);
+
+            let post = DiscussionPost {
+                author: author.clone(),
+                text,
+                posted_at: Self::current_block(),
+            };
+            <PostsByProposal<T>>::mutate(proposal_id, |posts| posts.push(post));
+            Self::deposit_event(RawEvent::DiscussionPostAdded(author, proposal_id, post_index));
+        }
+
+        /// Replace the description of an active proposal. It can be done only once.
+        /// A previous description is kept in the history of the proposal.
+        fn amend_description(origin, proposal_id: u32, new_description: Vec<u8>) {
+            let proposer = ensure_signed(origin)?;
+
             ensure!(<Proposals<T>>::exists(proposal_id), MSG_PROPOSAL_NOT_FOUND);
             let proposal = Self::proposals(proposal_id);
 
+            ensure!(proposer == proposal.proposer, MSG_YOU_DONT_OWN_THIS_PROPOSAL);
             ensure!(proposal.status == Active, MSG_PROPOSAL_FINALIZED);
+            ensure!(!<DescriptionHistory<T>>::exists(proposal_id), MSG_DESCRIPTION_ALREADY_AMENDED);
 
-            let not_expired = !Self::is_voting_period_expired(proposal.proposed_at);
-            ensure!(not_expired, MSG_PROPOSAL_EXPIRED);
+            ensure!(!new_description.is_empty(), MSG_EMPTY_DESCRIPTION_PROVIDED);
+            ensure!(new_description.len() as u32 <= Self::description_max_len(), MSG_TOO_LONG_DESCRIPTION);
 
-            let did_not_vote_before = !<VoteByAccountAndProposal<T>>::exists((voter.clone(), proposal_id));
-            ensure!(did_not_vote_before, MSG_YOU_ALREADY_VOTED);
+            // Both descriptions stay in storage, so a new one needs its own deposit:
+            let extra_deposit = Self::deposit_for_size(new_description.len());
+            T::Currency::reserve(&proposer, extra_deposit)
+                .map_err(|_| MSG_STAKE_IS_GREATER_THAN_BALANCE)?;
 
-            Self::_process_vote(voter, proposal_id, vote)?;
+            <DescriptionHistory<T>>::insert(proposal_id, vec![proposal.description]);
+            <Proposals<T>>::mutate(proposal_id, |p| {
+                p.description = new_description;
+                p.deposit = p.deposit + extra_deposit;
+            });
+            Self::deposit_event(RawEvent::DescriptionAmended(proposal_id));
         }
 
         // TODO add 'reason' why a proposer wants to cancel (UX + feedback)?
@@ -301,8 +979,8 @@ decl_module! {
             ensure!(proposal.status == Active, MSG_PROPOSAL_FINALIZED);
 
             // Spend some minimum fee on proposer's balance for canceling a proposal
-            let fee = Self::cancellation_fee();
-            let _ = T::Currency::slash_reserved(&proposer, fee);
+            let fee = rstd::cmp::min(Self::cancellation_fee(), proposal.stake);
+            Self::_slash_proposer(proposal_id, &proposer, fee);
 
             // Return unspent part of remaining staked deposit (after taking some fee)
             let left_stake = proposal.stake - fee;
@@ -319,24 +997,98 @@ decl_module! {
             }
         }
 
-        /// Cancel a proposal and return stake without slashing
-        fn veto_proposal(proposal_id: u32) {
+        /// Cancel a proposal and return stake without slashing.
+        /// An approved proposal can be cancelled until its grace period is over.
+        fn veto_proposal(origin, proposal_id: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
             ensure!(<Proposals<T>>::exists(proposal_id), MSG_PROPOSAL_NOT_FOUND);
             let proposal = Self::proposals(proposal_id);
-            ensure!(proposal.status == Active, MSG_PROPOSAL_FINALIZED);
 
-            let _ = T::Currency::unreserve(&proposal.proposer, proposal.stake);
+            if proposal.status == PendingExecution {
+                // Stake has been returned to proposer on approval.
+                Self::_unschedule_execution(proposal_id);
+                Self::_set_proposal_status(proposal_id, Cancelled);
+            } else {
+                ensure!(proposal.status == Active, MSG_PROPOSAL_FINALIZED);
 
-            Self::_update_proposal_status(proposal_id, Cancelled)?;
+                let _ = T::Currency::unreserve(&proposal.proposer, proposal.stake);
+
+                Self::_update_proposal_status(proposal_id, Cancelled)?;
+            }
 
             Self::deposit_event(RawEvent::ProposalVetoed(proposal_id));
         }
 
-        fn set_approval_quorum(new_value: u32) {
-            ensure!(new_value > 0, "approval quorom must be greater than zero");
-            <ApprovalQuorum<T>>::put(new_value);
+        /// Remove WASM code that is not referenced by any active or pending proposal.
+        fn purge_wasm_code(origin, wasm_hash: T::Hash) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(<WasmCodeByHash<T>>::exists(wasm_hash), MSG_WASM_CODE_NOT_FOUND);
+            ensure!(Self::wasm_code_ref_count(wasm_hash) == 0, MSG_WASM_CODE_IS_IN_USE);
+            Self::_remove_wasm_code(wasm_hash);
         }
-    }
+
+        fn set_retention_period(origin, new_value: T::BlockNumber) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            <RetentionPeriod<T>>::put(new_value);
+        }
+
+        fn set_grace_period(origin, new_value: T::BlockNumber) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            <GracePeriod<T>>::put(new_value);
+        }
+
+        fn set_deposit_per_byte(origin, new_value: BalanceOf<T>) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            <DepositPerByte<T>>::put(new_value);
+        }
+
+        fn set_max_delegation_depth(origin, new_value: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(new_value > 0, "max delegation depth must be greater than zero");
+            <MaxDelegationDepth<T>>::put(new_value);
+        }
+
+        fn set_max_followers_per_delegate(origin, new_value: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(new_value > 0, "max followers per delegate must be greater than zero");
+            <MaxFollowersPerDelegate<T>>::put(new_value);
+        }
+
+        fn set_max_advisory_votes_per_proposal(origin, new_value: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(new_value > 0, "max advisory votes per proposal must be greater than zero");
+            <MaxAdvisoryVotesPerProposal<T>>::put(new_value);
+        }
+
+        fn set_approval_quorum(origin, new_value: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(new_value > 0, "approval quorom must be greater than zero");
+            <ApprovalQuorum<T>>::put(new_value);
+        }
+
+        fn set_approval_threshold(origin, new_value: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(new_value <= 100, MSG_INVALID_APPROVAL_THRESHOLD);
+            <ApprovalThreshold<T>>::put(new_value);
+        }
+
+        /// Set voting rules for proposals of a given category.
+        fn set_proposal_settings(
+            origin,
+            category: ProposalCategory,
+            settings: ProposalSettings<BalanceOf<T>, T::BlockNumber>
+        ) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            Self::_validate_settings(&settings)?;
+            <SettingsByCategory<T>>::insert(category, settings);
+        }
+
+        /// Make proposals of a given category use the global parameters of this module.
+        fn reset_proposal_settings(origin, category: ProposalCategory) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            <SettingsByCategory<T>>::remove(category);
+        }
+    }
 }
 
 impl<T: Trait> Module<T> {
@@ -345,9 +1097,8 @@ impl<T: Trait> Module<T> {
         <system::Module<T>>::block_number()
     }
 
-    // TODO This method should be moved to Membership module once it's created.
     fn is_member(sender: T::AccountId) -> bool {
-        !T::Currency::free_balance(&sender).is_zero()
+        T::Members::is_active_member(&sender)
     }
 
     fn is_councilor(sender: &T::AccountId) -> bool {
@@ -358,12 +1109,329 @@ impl<T: Trait> Module<T> {
         <council::Module<T>>::active_council().len() as u32
     }
 
-    fn approval_quorum_seats() -> u32 {
-        (Self::approval_quorum() * Self::councilors_count()) / 100
+    /// Sum up stakes of council seats by votes of their councilors on a proposal.
+    pub fn vote_stakes(proposal_id: u32) -> VoteStakes<BalanceOf<T>> {
+        let mut stakes = VoteStakes::default();
+        for seat in <council::Module<T>>::active_council() {
+            let seat_stake = seat.calc_total_stake();
+            stakes.council_stake = stakes.council_stake + seat_stake;
+
+            let vote_key = (seat.member, proposal_id);
+            if !<VoteByAccountAndProposal<T>>::exists(&vote_key) {
+                continue;
+            }
+            let total = match <VoteByAccountAndProposal<T>>::get(&vote_key) {
+                Abstain => &mut stakes.abstentions,
+                Approve => &mut stakes.approvals,
+                Reject => &mut stakes.rejections,
+                Slash => &mut stakes.slashes,
+            };
+            *total = *total + seat_stake;
+        }
+        stakes
+    }
+
+    /// Count votes on a proposal cast by accounts that are councilors now.
+    fn council_vote_counts(proposal_id: u32) -> VoteCounts {
+        let mut counts = VoteCounts::default();
+        for (voter, vote) in Self::votes_by_proposal(proposal_id) {
+            if Self::is_councilor(&voter) {
+                counts.add(&vote);
+            }
+        }
+        counts
+    }
+
+    fn approval_quorum_seats(category: ProposalCategory) -> u32 {
+        (Self::settings(category).quorum_percent * Self::councilors_count()) / 100
+    }
+
+    fn is_voting_period_expired(expires_at: T::BlockNumber) -> bool {
+        Self::current_block() >= expires_at
+    }
+
+    /// Version of the running runtime that a runtime upgrade is compared with.
+    fn current_spec_version() -> SpecVersion {
+        SpecVersion {
+            spec_name: crate::VERSION.spec_name.as_bytes().to_vec(),
+            spec_version: crate::VERSION.spec_version,
+        }
+    }
+
+    /// Storage deposit for a proposal that occupies a given number of bytes.
+    pub fn deposit_for_size(bytes: usize) -> BalanceOf<T> {
+        Self::deposit_per_byte() * BalanceOf::<T>::sa(bytes as u64)
+    }
+
+    /// Voting rules of a given proposal category. Categories that have no own settings
+    /// use the global parameters of this module.
+    pub fn settings(category: ProposalCategory) -> ProposalSettings<BalanceOf<T>, T::BlockNumber> {
+        if <SettingsByCategory<T>>::exists(category) {
+            Self::settings_by_category(category)
+        } else {
+            ProposalSettings {
+                quorum_percent: Self::approval_quorum(),
+                approval_threshold_percent: Self::approval_threshold(),
+                voting_period: Self::voting_period(),
+                min_stake: Self::min_stake(),
+                tally_mode: TallyMode::Count,
+            }
+        }
+    }
+
+    fn _validate_settings(settings: &ProposalSettings<BalanceOf<T>, T::BlockNumber>) -> Result {
+        let quorum = settings.quorum_percent;
+        ensure!(quorum > 0 && quorum <= 100, MSG_INVALID_APPROVAL_QUORUM);
+        ensure!(settings.approval_threshold_percent <= 100, MSG_INVALID_APPROVAL_THRESHOLD);
+        ensure!(!settings.voting_period.is_zero(), MSG_ZERO_VOTING_PERIOD);
+        ensure!(settings.min_stake >= Self::cancellation_fee(), MSG_FEE_IS_GREATER_THAN_MIN_STAKE);
+        ensure!(settings.min_stake >= Self::rejection_fee(), MSG_FEE_IS_GREATER_THAN_MIN_STAKE);
+        Ok(())
+    }
+
+    /// Create a new proposal. A part of the stake could be reserved already
+    /// if it has been moved from a superseded proposal. Returns id of the new proposal.
+    fn _create_proposal(
+        proposer: T::AccountId,
+        stake: BalanceOf<T>,
+        reserved_stake: BalanceOf<T>,
+        name: Vec<u8>,
+        description: Vec<u8>,
+        kind: ProposalKind<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>,
+        wasm_code: Option<Vec<u8>>
+    ) -> rstd::result::Result<u32, &'static str> {
+        ensure!(Self::is_member(proposer.clone()), MSG_ONLY_MEMBERS_CAN_PROPOSE);
+        ensure!(stake >= Self::settings(kind.category()).min_stake, MSG_STAKE_IS_TOO_LOW);
+
+        ensure!(!name.is_empty(), MSG_EMPTY_NAME_PROVIDED);
+        ensure!(name.len() as u32 <= Self::name_max_len(), MSG_TOO_LONG_NAME);
+
+        ensure!(!description.is_empty(), MSG_EMPTY_DESCRIPTION_PROVIDED);
+        ensure!(description.len() as u32 <= Self::description_max_len(), MSG_TOO_LONG_DESCRIPTION);
+
+        Self::_validate_proposal_kind(&kind, &wasm_code)?;
+
+        let wasm_code_len = wasm_code.as_ref().map_or(0, |code| code.len());
+        let deposit = Self::deposit_for_size(name.len() + description.len() + wasm_code_len);
+
+        // Lock proposer's stake together with a storage deposit:
+        T::Currency::reserve(&proposer, stake - reserved_stake + deposit)
+            .map_err(|_| MSG_STAKE_IS_GREATER_THAN_BALANCE)?;
+
+        let proposal_id = Self::proposal_count() + 1;
+        <ProposalCount<T>>::put(proposal_id);
+
+        let proposed_at = Self::current_block();
+        let expires_at = proposed_at + Self::settings(kind.category()).voting_period;
+
+        if let ProposalKind::RuntimeUpgrade(wasm_hash) = &kind {
+            if let Some(wasm_code) = wasm_code {
+                if !<WasmCodeByHash<T>>::exists(wasm_hash) {
+                    <WasmCodeByHash<T>>::insert(wasm_hash, wasm_code);
+                }
+            }
+            <WasmCodeRefCount<T>>::mutate(wasm_hash, |count| *count += 1);
+        }
+
+        let new_proposal = Proposal {
+            id: proposal_id,
+            proposer: proposer.clone(),
+            stake,
+            deposit,
+            name,
+            description,
+            kind,
+            proposed_at,
+            expires_at,
+            status: Active,
+            superseded_by: None
+        };
+
+        <Proposals<T>>::insert(proposal_id, new_proposal);
+        <ActiveProposalIds<T>>::mutate(|ids| ids.push(proposal_id));
+        <ProposalIdsByExpiry<T>>::mutate(expires_at, |ids| ids.push(proposal_id));
+        Self::_add_to_proposer_index(proposal_id, &proposer);
+        Self::_add_to_status_index(proposal_id, Active);
+        Self::deposit_event(RawEvent::ProposalCreated(proposer.clone(), proposal_id));
+
+        // Auto-vote with Approve if proposer is a councilor:
+        if Self::is_councilor(&proposer) {
+            Self::_process_vote(proposer, proposal_id, Approve)?;
+        }
+
+        Ok(proposal_id)
+    }
+
+    fn _supersede_proposal(
+        proposer: T::AccountId,
+        proposal_id: u32,
+        name: Vec<u8>,
+        description: Vec<u8>,
+        kind: ProposalKind<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>,
+        wasm_code: Option<Vec<u8>>
+    ) -> Result {
+        ensure!(<Proposals<T>>::exists(proposal_id), MSG_PROPOSAL_NOT_FOUND);
+        let proposal = Self::proposals(proposal_id);
+
+        ensure!(proposer == proposal.proposer, MSG_YOU_DONT_OWN_THIS_PROPOSAL);
+        ensure!(proposal.status == Active, MSG_PROPOSAL_FINALIZED);
+
+        // A new proposal is created first, so WASM code shared by both proposals is not released:
+        let stake = proposal.stake;
+        let new_proposal_id = Self::_create_proposal(
+            proposer, stake, stake, name, description, kind, wasm_code)?;
+
+        <Proposals<T>>::mutate(proposal_id, |p| p.superseded_by = Some(new_proposal_id));
+        Self::_update_proposal_status(proposal_id, Superseded)?;
+        Self::deposit_event(RawEvent::ProposalSuperseded(proposal_id, new_proposal_id));
+
+        Ok(())
+    }
+
+    /// Kind-specific checks of a new proposal.
+    /// WASM code is provided only when it is uploaded along with a runtime upgrade proposal.
+    fn _validate_proposal_kind(
+        kind: &ProposalKind<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>,
+        wasm_code: &Option<Vec<u8>>
+    ) -> Result {
+        match kind {
+            ProposalKind::RuntimeUpgrade(wasm_hash) => {
+                if let Some(wasm_code) = wasm_code {
+                    ensure!(!wasm_code.is_empty(), MSG_EMPTY_WASM_CODE_PROVIDED);
+                    ensure!(wasm_code.len() as u32 <= Self::wasm_code_max_len(), MSG_TOO_LONG_WASM_CODE);
+                    runtime_code::validate(wasm_code, &Self::current_spec_version())?;
+                } else {
+                    ensure!(<WasmCodeByHash<T>>::exists(wasm_hash), MSG_WASM_CODE_NOT_FOUND);
+                }
+            },
+            ProposalKind::Text => { /* nothing */ },
+            ProposalKind::ParameterChange(parameter) => Self::_validate_parameter(parameter)?,
+            ProposalKind::TreasurySpend(_, amount) => {
+                ensure!(!amount.is_zero(), MSG_ZERO_TREASURY_SPEND);
+            },
+            ProposalKind::AddCouncilMember(account) => {
+                ensure!(!Self::is_councilor(account), MSG_ACCOUNT_IS_COUNCILOR);
+            },
+            ProposalKind::RemoveCouncilMember(account) => {
+                ensure!(Self::is_councilor(account), MSG_ACCOUNT_IS_NOT_COUNCILOR);
+            },
+        }
+        Ok(())
+    }
+
+    fn _validate_parameter(parameter: &ProposalParameter<BalanceOf<T>, T::BlockNumber>) -> Result {
+        match parameter {
+            ProposalParameter::ApprovalQuorum(quorum) => {
+                ensure!(*quorum > 0 && *quorum <= 100, MSG_INVALID_APPROVAL_QUORUM);
+            },
+            ProposalParameter::MinStake(min_stake) => {
+                // Fees are taken from a proposal stake, so they cannot exceed it:
+                ensure!(*min_stake >= Self::cancellation_fee(), MSG_FEE_IS_GREATER_THAN_MIN_STAKE);
+                ensure!(*min_stake >= Self::rejection_fee(), MSG_FEE_IS_GREATER_THAN_MIN_STAKE);
+            },
+            ProposalParameter::CancellationFee(fee) | ProposalParameter::RejectionFee(fee) => {
+                ensure!(*fee <= Self::min_stake(), MSG_FEE_IS_GREATER_THAN_MIN_STAKE);
+            },
+            ProposalParameter::VotingPeriod(period) => {
+                ensure!(!period.is_zero(), MSG_ZERO_VOTING_PERIOD);
+            },
+        }
+        Ok(())
+    }
+
+    /// Count stakes of members behind votes on a proposal. A member who has not voted directly
+    /// follows the vote of the nearest voter up the delegation chain, within the max delegation depth.
+    /// Votes of councilors count as advisory votes of themselves and their delegators.
+    /// Every follower is visited once at most, so the work is bounded by the number of direct votes
+    /// times the max number of followers per delegate.
+    pub fn advisory_tally(proposal_id: u32) -> AdvisoryTally<BalanceOf<T>> {
+        let mut tally = AdvisoryTally::default();
+
+        let mut direct_votes = Self::votes_by_proposal(proposal_id);
+        direct_votes.extend(Self::advisory_votes_by_proposal(proposal_id));
+        let mut direct_voters = BTreeSet::new();
+        for (voter, vote) in direct_votes.iter() {
+            direct_voters.insert(voter.clone());
+            tally.add(vote, T::Currency::total_balance(voter));
+        }
+
+        let mut followed_votes = direct_votes;
+        for _ in 0..Self::max_delegation_depth() {
+            let mut next_votes = vec![];
+            for (delegate, vote) in followed_votes.iter() {
+                for delegator in Self::signal_delegators(delegate) {
+                    if direct_voters.contains(&delegator) || !Self::is_member(delegator.clone()) {
+                        continue;
+                    }
+                    tally.add(vote, T::Currency::total_balance(&delegator));
+                    next_votes.push((delegator, vote.clone()));
+                }
+            }
+            if next_votes.is_empty() {
+                break;
+            }
+            followed_votes = next_votes;
+        }
+
+        tally
+    }
+
+    fn _ensure_can_signal(account: &T::AccountId) -> Result {
+        ensure!(!Self::is_councilor(account), MSG_COUNCILORS_CANNOT_SIGNAL);
+        ensure!(Self::is_member(account.clone()), MSG_ONLY_MEMBERS_CAN_SIGNAL);
+        Ok(())
+    }
+
+    fn _remove_signal_delegation(delegator: &T::AccountId) {
+        if let Some(old_delegate) = <SignalDelegateOf<T>>::take(delegator) {
+            <SignalDelegators<T>>::mutate(&old_delegate, |delegators| delegators.retain(|d| d != delegator));
+            let moved = 1 + Self::signal_follower_count(delegator);
+            for account in Self::_signal_delegation_chain(&old_delegate) {
+                <SignalFollowerCount<T>>::mutate(&account, |count| *count = count.saturating_sub(moved));
+            }
+            Self::_update_delegation_heights(&old_delegate);
+        }
+    }
+
+    /// A given account and the accounts it follows up its delegation chain.
+    /// The chain is not longer than the max delegation depth.
+    fn _signal_delegation_chain(account: &T::AccountId) -> Vec<T::AccountId> {
+        let mut chain = vec![account.clone()];
+        let mut next = Self::signal_delegate_of(account);
+        while let Some(account) = next {
+            next = Self::signal_delegate_of(&account);
+            chain.push(account);
+        }
+        chain
+    }
+
+    /// Recalculate delegation heights of an account whose delegators have changed
+    /// and of the accounts up its delegation chain, until a height stays the same.
+    fn _update_delegation_heights(account: &T::AccountId) {
+        let mut next = Some(account.clone());
+        while let Some(account) = next {
+            let height = Self::signal_delegators(&account).iter()
+                .map(|delegator| Self::signal_delegation_height(delegator) + 1)
+                .max()
+                .unwrap_or(0);
+            if height == Self::signal_delegation_height(&account) {
+                break;
+            }
+            <SignalDelegationHeight<T>>::insert(&account, height);
+            next = Self::signal_delegate_of(&account);
+        }
     }
 
-    fn is_voting_period_expired(proposed_at: T::BlockNumber) -> bool {
-        Self::current_block() >= proposed_at + Self::voting_period()
+    fn _ensure_voting_is_open(proposal_id: u32) -> Result {
+        ensure!(<Proposals<T>>::exists(proposal_id), MSG_PROPOSAL_NOT_FOUND);
+        let proposal = Self::proposals(proposal_id);
+
+        ensure!(proposal.status == Active, MSG_PROPOSAL_FINALIZED);
+
+        let not_expired = !Self::is_voting_period_expired(proposal.expires_at);
+        ensure!(not_expired, MSG_PROPOSAL_EXPIRED);
+
+        Ok(())
     }
 
     fn _process_vote(voter: T::AccountId, proposal_id: u32, vote: VoteKind) -> Result {
@@ -376,55 +1444,135 @@ impl<T: Trait> Module<T> {
             <VotesByProposal<T>>::insert(proposal_id, vec![new_vote]);
         }
         <VoteByAccountAndProposal<T>>::insert((voter.clone(), proposal_id), &vote);
+        <VoteCountsByProposal<T>>::mutate(proposal_id, |counts| counts.add(&vote));
+        Self::_schedule_tally(proposal_id);
         Self::deposit_event(RawEvent::Voted(voter, proposal_id, vote));
         Ok(())
     }
 
+    /// Make the next tally evaluate a proposal whose votes have changed.
+    fn _schedule_tally(proposal_id: u32) {
+        <ProposalIdsToTally<T>>::mutate(|ids| {
+            if !ids.contains(&proposal_id) {
+                ids.push(proposal_id);
+            }
+        });
+    }
+
     fn end_block(now: T::BlockNumber) -> Result {
 
-        // TODO refactor this method
+        Self::tally(now)?;
 
-        // TODO iterate over not expired proposals and tally
+        Self::_execute_scheduled_proposals(now);
 
-          Self::tally()?;
-          // TODO approve or reject a proposal
+        Self::_prune_proposals(now);
 
         Ok(())
     }
 
-    /// Get the voters for the current proposal.
-    pub fn tally(/* proposal_id: u32 */) -> Result {
-
-        let councilors: u32 = Self::councilors_count();
-        let quorum: u32 = Self::approval_quorum_seats();
+    /// Remove details and votes of proposals whose retention period ends at a given block.
+    fn _prune_proposals(now: T::BlockNumber) {
+        for proposal_id in <ProposalIdsToPrune<T>>::take(now) {
+            for (voter, _) in <VotesByProposal<T>>::take(proposal_id) {
+                <VoteByAccountAndProposal<T>>::remove((voter, proposal_id));
+            }
+            <VoteCountsByProposal<T>>::remove(proposal_id);
+            <TallyResults<T>>::remove(proposal_id);
+            <PostsByProposal<T>>::remove(proposal_id);
+            <DescriptionHistory<T>>::remove(proposal_id);
+            <AdvisoryVotesByProposal<T>>::remove(proposal_id);
+            <AdvisoryTallyResults<T>>::remove(proposal_id);
+            let proposal = <Proposals<T>>::take(proposal_id);
+            Self::_remove_from_proposer_index(proposal_id, &proposal.proposer);
+            Self::_remove_from_status_index(proposal_id, proposal.status);
+            Self::deposit_event(RawEvent::ProposalPruned(proposal_id));
+        }
+    }
 
-        for &proposal_id in Self::active_proposal_ids().iter() {
-            let votes = Self::votes_by_proposal(proposal_id);
-            let mut abstentions: u32 = 0;
-            let mut approvals: u32 = 0;
-            let mut rejections: u32 = 0;
-            let mut slashes: u32 = 0;
+    /// Execute approved proposals whose grace period ends at a given block.
+    fn _execute_scheduled_proposals(now: T::BlockNumber) {
+        for proposal_id in <ScheduledExecutions<T>>::take(now) {
+            <ExecutesAt<T>>::remove(proposal_id);
 
-            for (_, vote) in votes.iter() {
-                match vote {
-                    Abstain => abstentions += 1,
-                    Approve => approvals += 1,
-                    Reject => rejections += 1,
-                    Slash => slashes += 1,
+            let proposal = Self::proposals(proposal_id);
+            match Self::_execute_proposal(proposal_id, proposal.kind) {
+                Ok(_) => {
+                    Self::_set_proposal_status(proposal_id, Approved);
+                    Self::deposit_event(RawEvent::ProposalExecuted(proposal_id));
+                },
+                Err(e) => {
+                    Self::_set_proposal_status(proposal_id, ExecutionFailed);
+                    Self::deposit_event(RawEvent::ProposalExecutionFailed(proposal_id, e.as_bytes().to_vec()));
                 }
             }
+        }
+    }
+
+    fn _unschedule_execution(proposal_id: u32) {
+        let executes_at = <ExecutesAt<T>>::take(proposal_id);
+        <ScheduledExecutions<T>>::mutate(executes_at, |ids| ids.retain(|&id| id != proposal_id));
+    }
+
+    /// Evaluate proposals that received votes since the last tally
+    /// and proposals whose voting period ends at a given block.
+    pub fn tally(now: T::BlockNumber) -> Result {
+
+        let mut proposal_ids = <ProposalIdsToTally<T>>::take();
+        for proposal_id in <ProposalIdsByExpiry<T>>::take(now) {
+            if !proposal_ids.contains(&proposal_id) {
+                proposal_ids.push(proposal_id);
+            }
+        }
 
+        for proposal_id in proposal_ids {
             let proposal = Self::proposals(proposal_id);
-            let is_expired = Self::is_voting_period_expired(proposal.proposed_at);
+            if proposal.status != Active {
+                // This proposal has been finalized since it was scheduled for a tally.
+                continue;
+            }
+
+            // The council can change while a proposal is open, so only votes
+            // of current councilors count and the council size is read for every proposal.
+            let councilors: u32 = Self::councilors_count();
+            let counts = Self::council_vote_counts(proposal_id);
+            let votes_count = counts.total();
+            let VoteCounts { abstentions, approvals, rejections, slashes } = counts;
+
+            let category = proposal.kind.category();
+            let settings = Self::settings(category);
+            let quorum: u32 = Self::approval_quorum_seats(category);
+            let is_expired = Self::is_voting_period_expired(proposal.expires_at);
 
             // We need to check that the council is not empty because otherwise,
             // if there is no votes on a proposal it will be counted as if
             // all 100% (zero) councilors voted on the proposal and should be approved.
 
             let non_empty_council = councilors > 0;
-            let all_councilors_voted = non_empty_council && votes.len() as u32 == councilors;
+            let all_councilors_voted = non_empty_council && votes_count == councilors;
             let all_councilors_slashed = non_empty_council && slashes == councilors;
-            let quorum_reached = quorum > 0 && approvals >= quorum;
+
+            let weighted = match settings.tally_mode {
+                TallyMode::Count => None,
+                TallyMode::StakeWeighted => Some(Self::vote_stakes(proposal_id)),
+            };
+
+            let quorum_reached = match weighted {
+                Some(ref stakes) if !stakes.council_stake.is_zero() => {
+                    let percent_of = |stake: BalanceOf<T>, percent: u32| stake * BalanceOf::<T>::sa(percent as u64);
+                    let approvals_percent = percent_of(stakes.approvals, 100);
+                    let non_abstentions = stakes.approvals + stakes.rejections + stakes.slashes;
+                    let threshold_reached = approvals_percent >= percent_of(non_abstentions, settings.approval_threshold_percent);
+                    !stakes.approvals.is_zero() &&
+                        approvals_percent >= percent_of(stakes.council_stake, settings.quorum_percent) &&
+                        threshold_reached
+                },
+                // Seats without stakes (e.g. of a council set by root) are counted by votes.
+                _ => {
+                    let non_abstentions = approvals + rejections + slashes;
+                    let threshold_reached = approvals * 100 >= settings.approval_threshold_percent * non_abstentions;
+                    quorum > 0 && approvals >= quorum && threshold_reached
+                }
+            };
 
             // Don't approve a proposal right after quorum reached
             // if not all councilors casted their votes.
@@ -454,18 +1602,33 @@ impl<T: Trait> Module<T> {
 
             // TODO move next block outside of tally to 'end_block'
             if let Some(status) = new_status {
-                Self::_update_proposal_status(proposal_id, status.clone())?;
+                // Ids have been taken from storage already, so a failure of one proposal
+                // must not prevent other proposals from being finalized. The failed proposal
+                // is tallied again in the next block rather than left active forever.
+                if let Err(e) = Self::_update_proposal_status(proposal_id, status.clone()) {
+                    print(e);
+                    Self::_schedule_tally(proposal_id);
+                    continue;
+                }
                 let tally_result = TallyResult {
                     proposal_id,
                     abstentions,
                     approvals,
                     rejections,
                     slashes,
+                    weighted,
                     status,
                     finalized_at: Self::current_block(),
                 };
                 <TallyResults<T>>::insert(proposal_id, &tally_result);
+                <AdvisoryTallyResults<T>>::insert(proposal_id, Self::advisory_tally(proposal_id));
                 Self::deposit_event(RawEvent::TallyFinalized(tally_result));
+
+                let voters: Vec<T::AccountId> = Self::votes_by_proposal(proposal_id)
+                    .into_iter()
+                    .map(|(voter, _)| voter)
+                    .collect();
+                <council::Module<T>>::record_proposal_participation(&voters);
             }
         }
 
@@ -491,21 +1654,155 @@ impl<T: Trait> Module<T> {
                 Slashed => Self::_slash_proposal(pid)?,
                 Rejected | Expired => Self::_reject_proposal(pid)?,
                 Approved => Self::_approve_proposal(pid)?,
-                Active | Cancelled => { /* nothing */ },
+                // Stake of a superseded proposal has been moved to a new proposal.
+                Active | Cancelled | PendingExecution | ExecutionFailed | Superseded => { /* nothing */ },
             }
+            Self::_settle_deposit(pid, &new_status);
+
+            // An approved proposal waits for the grace period before it gets executed:
+            let new_status = if new_status == Approved { PendingExecution } else { new_status };
+
             <ActiveProposalIds<T>>::put(other_active_ids);
-            <Proposals<T>>::mutate(proposal_id, |p| p.status = new_status.clone());
-            Self::deposit_event(RawEvent::ProposalStatusUpdated(proposal_id, new_status));
+            let expires_at = Self::proposals(proposal_id).expires_at;
+            <ProposalIdsByExpiry<T>>::mutate(expires_at, |ids| ids.retain(|&id| id != proposal_id));
+            Self::_set_proposal_status(proposal_id, new_status);
             Ok(())
         }
     }
 
+    fn _set_proposal_status(proposal_id: u32, new_status: ProposalStatus) {
+        let old_status = Self::proposals(proposal_id).status;
+        <Proposals<T>>::mutate(proposal_id, |p| p.status = new_status.clone());
+
+        Self::_remove_from_status_index(proposal_id, old_status);
+        Self::_add_to_status_index(proposal_id, new_status.clone());
+
+        let is_final = match new_status {
+            Active | PendingExecution => false,
+            _ => true,
+        };
+        if is_final {
+            let proposal = Self::proposals(proposal_id);
+            if let ProposalKind::RuntimeUpgrade(wasm_hash) = proposal.kind {
+                Self::_release_wasm_code(wasm_hash);
+            }
+
+            let now = Self::current_block();
+            <ProposalSummaries<T>>::insert(proposal_id, ProposalSummary {
+                id: proposal_id,
+                proposer: proposal.proposer,
+                status: new_status.clone(),
+                votes: Self::vote_counts_by_proposal(proposal_id),
+                finalized_at: now,
+            });
+            let prune_at = now + Self::retention_period();
+            <ProposalIdsToPrune<T>>::mutate(prune_at, |ids| ids.push(proposal_id));
+        }
+
+        Self::deposit_event(RawEvent::ProposalStatusUpdated(proposal_id, new_status));
+    }
+
+    /// Append a proposal to the index of its proposer.
+    fn _add_to_proposer_index(proposal_id: u32, proposer: &T::AccountId) {
+        let position = Self::proposal_count_by_proposer(proposer);
+        <ProposalIdByProposer<T>>::insert((proposer.clone(), position), proposal_id);
+        <ProposerPositionOfProposal<T>>::insert(proposal_id, position);
+        <ProposalCountByProposer<T>>::insert(proposer, position + 1);
+    }
+
+    /// Remove a proposal from the index of its proposer. The last proposal of the proposer
+    /// takes its position, so the index does not need to be shifted.
+    fn _remove_from_proposer_index(proposal_id: u32, proposer: &T::AccountId) {
+        let last_position = Self::proposal_count_by_proposer(proposer).saturating_sub(1);
+        let position = <ProposerPositionOfProposal<T>>::take(proposal_id);
+        let last_id = <ProposalIdByProposer<T>>::take((proposer.clone(), last_position));
+        if last_id != proposal_id {
+            <ProposalIdByProposer<T>>::insert((proposer.clone(), position), last_id);
+            <ProposerPositionOfProposal<T>>::insert(last_id, position);
+        }
+        if last_position == 0 {
+            <ProposalCountByProposer<T>>::remove(proposer);
+        } else {
+            <ProposalCountByProposer<T>>::insert(proposer, last_position);
+        }
+    }
+
+    /// Append a proposal to the index of a status.
+    fn _add_to_status_index(proposal_id: u32, status: ProposalStatus) {
+        let position = Self::proposal_count_by_status(&status);
+        <ProposalIdByStatus<T>>::insert((status.clone(), position), proposal_id);
+        <StatusPositionOfProposal<T>>::insert(proposal_id, position);
+        <ProposalCountByStatus<T>>::insert(status, position + 1);
+    }
+
+    /// Remove a proposal from the index of a status. The last proposal of the status
+    /// takes its position, so the index does not need to be shifted.
+    fn _remove_from_status_index(proposal_id: u32, status: ProposalStatus) {
+        let last_position = Self::proposal_count_by_status(&status).saturating_sub(1);
+        let position = <StatusPositionOfProposal<T>>::take(proposal_id);
+        let last_id = <ProposalIdByStatus<T>>::take((status.clone(), last_position));
+        if last_id != proposal_id {
+            <ProposalIdByStatus<T>>::insert((status.clone(), position), last_id);
+            <StatusPositionOfProposal<T>>::insert(last_id, position);
+        }
+        <ProposalCountByStatus<T>>::insert(status, last_position);
+    }
+
+    /// Ids of all proposals created by a given account. Intended for queries outside of the runtime.
+    pub fn proposal_ids_by_proposer(proposer: T::AccountId) -> Vec<u32> {
+        (0..Self::proposal_count_by_proposer(&proposer))
+            .map(|position| Self::proposal_id_by_proposer((proposer.clone(), position)))
+            .collect()
+    }
+
+    /// Ids of all proposals that have a given status. Intended for queries outside of the runtime.
+    pub fn proposal_ids_by_status(status: ProposalStatus) -> Vec<u32> {
+        (0..Self::proposal_count_by_status(&status))
+            .map(|position| Self::proposal_id_by_status((status.clone(), position)))
+            .collect()
+    }
+
+    /// Drop a reference of a finalized proposal to WASM code
+    /// and remove the code if no other proposal refers to it.
+    fn _release_wasm_code(wasm_hash: T::Hash) {
+        let count = Self::wasm_code_ref_count(wasm_hash).saturating_sub(1);
+        if count == 0 {
+            Self::_remove_wasm_code(wasm_hash);
+        } else {
+            <WasmCodeRefCount<T>>::insert(wasm_hash, count);
+        }
+    }
+
+    fn _remove_wasm_code(wasm_hash: T::Hash) {
+        <WasmCodeRefCount<T>>::remove(wasm_hash);
+        <WasmCodeByHash<T>>::remove(wasm_hash);
+        Self::deposit_event(RawEvent::WasmCodeRemoved(wasm_hash));
+    }
+
+    /// Return a storage deposit to a proposer unless the proposal has been slashed.
+    /// A slashed proposal loses its deposit together with the stake.
+    fn _settle_deposit(proposal_id: u32, status: &ProposalStatus) {
+        if *status != Slashed {
+            let proposal = Self::proposals(proposal_id);
+            let _ = T::Currency::unreserve(&proposal.proposer, proposal.deposit);
+        }
+    }
+
+    /// Pass a part of proposer's reserved balance to the slash handler.
+    fn _slash_proposer(proposal_id: u32, proposer: &T::AccountId, amount: BalanceOf<T>) {
+        if amount.is_zero() {
+            return;
+        }
+        let recipients = T::OnProposalSlash::on_proposal_slash(proposal_id, proposer, amount);
+        Self::deposit_event(RawEvent::ProposerSlashed(proposal_id, amount, recipients));
+    }
+
     /// Slash a proposal. The staked deposit will be slashed.
     fn _slash_proposal(proposal_id: u32) -> Result {
         let proposal = Self::proposals(proposal_id);
 
-        // Slash proposer's stake:
-        let _ = T::Currency::slash_reserved(&proposal.proposer, proposal.stake);
+        // Slash proposer's stake and storage deposit:
+        Self::_slash_proposer(proposal_id, &proposal.proposer, proposal.stake + proposal.deposit);
 
         Ok(())
     }
@@ -516,8 +1813,8 @@ impl<T: Trait> Module<T> {
         let proposer = proposal.proposer;
 
         // Spend some minimum fee on proposer's balance to prevent spamming attacks:
-        let fee = Self::rejection_fee();
-        let _ = T::Currency::slash_reserved(&proposer, fee);
+        let fee = rstd::cmp::min(Self::rejection_fee(), proposal.stake);
+        Self::_slash_proposer(proposal_id, &proposer, fee);
 
         // Return unspent part of remaining staked deposit (after taking some fee):
         let left_stake = proposal.stake - fee;
@@ -526,18 +1823,72 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
-    /// Approve a proposal. The staked deposit will be returned.
+    /// Approve a proposal. The staked deposit will be returned
+    /// and the proposal will be executed after the grace period.
     fn _approve_proposal(proposal_id: u32) -> Result {
         let proposal = Self::proposals(proposal_id);
-        let wasm_code = Self::wasm_code_by_hash(proposal.wasm_hash);
 
         // Return staked deposit to proposer:
         let _ = T::Currency::unreserve(&proposal.proposer, proposal.stake);
 
-        // Update wasm code of node's runtime:
-        <consensus::Module<T>>::set_code(wasm_code)?;
+        let executes_at = Self::current_block() + Self::grace_period();
+        <ScheduledExecutions<T>>::mutate(executes_at, |ids| ids.push(proposal_id));
+        <ExecutesAt<T>>::insert(proposal_id, executes_at);
+        Self::deposit_event(RawEvent::ProposalScheduled(proposal_id, executes_at));
+
+        Ok(())
+    }
+
+    /// Execute an approved proposal according to its kind.
+    fn _execute_proposal(
+        proposal_id: u32,
+        kind: ProposalKind<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>
+    ) -> Result {
+        match kind {
+            ProposalKind::RuntimeUpgrade(wasm_hash) => {
+                let wasm_code = Self::wasm_code_by_hash(wasm_hash);
+
+                // Update wasm code of node's runtime:
+                <consensus::Module<T>>::set_code(wasm_code)?;
+
+                Self::deposit_event(RawEvent::RuntimeUpdated(proposal_id, wasm_hash));
+            },
+            ProposalKind::Text => { /* nothing */ },
+            ProposalKind::ParameterChange(parameter) => {
+                // Other parameters could have been changed while this proposal was active:
+                Self::_validate_parameter(&parameter)?;
+                match parameter {
+                    ProposalParameter::ApprovalQuorum(quorum) => <ApprovalQuorum<T>>::put(quorum),
+                    ProposalParameter::MinStake(min_stake) => <MinStake<T>>::put(min_stake),
+                    ProposalParameter::CancellationFee(fee) => <CancellationFee<T>>::put(fee),
+                    ProposalParameter::RejectionFee(fee) => <RejectionFee<T>>::put(fee),
+                    ProposalParameter::VotingPeriod(period) => <VotingPeriod<T>>::put(period),
+                }
+                Self::deposit_event(RawEvent::ParameterUpdated(proposal_id));
+            },
+            ProposalKind::TreasurySpend(destination, amount) => {
+                let treasury = Self::treasury_account();
+                ensure!(T::Currency::free_balance(&treasury) >= amount, MSG_TREASURY_BALANCE_TOO_LOW);
+
+                // Move the funds through the reserved balance of the treasury,
+                // so total issuance stays the same:
+                T::Currency::reserve(&treasury, amount)?;
+                if let Err(e) = T::Currency::repatriate_reserved(&treasury, &destination, amount) {
+                    let _ = T::Currency::unreserve(&treasury, amount);
+                    return Err(e);
+                }
 
-        Self::deposit_event(RawEvent::RuntimeUpdated(proposal_id, proposal.wasm_hash));
+                Self::deposit_event(RawEvent::TreasurySpent(proposal_id, destination, amount));
+            },
+            ProposalKind::AddCouncilMember(account) => {
+                <council::Module<T>>::try_add_council_member(account.clone())?;
+                Self::deposit_event(RawEvent::CouncilMemberAdded(proposal_id, account));
+            },
+            ProposalKind::RemoveCouncilMember(account) => {
+                <council::Module<T>>::try_remove_council_member(account.clone())?;
+                Self::deposit_event(RawEvent::CouncilMemberRemoved(proposal_id, account));
+            },
+        }
 
         Ok(())
     }
@@ -560,7 +1911,9 @@ mod tests {
     use srml_support::*;
 
     impl_outer_origin! {
-        pub enum Origin for Test {}
+        pub enum Origin for Test {
+            council
+        }
     }
 
     // For testing the module, we construct most of a mock runtime. This means
@@ -605,6 +1958,7 @@ mod tests {
     impl council::Trait for Test {
         type Event = ();
         type CouncilTermEnded = ();
+        type CouncilOrigin = council::RootOrCouncilMajority<Test>;
     }
 
     impl GovernanceCurrency for Test {
@@ -613,6 +1967,15 @@ mod tests {
 
     impl Trait for Test {
         type Event = ();
+        type OnProposalSlash = ();
+        type Members = MockMembership;
+    }
+
+    pub struct MockMembership;
+    impl Members<Test> for MockMembership {
+        fn is_active_member(account: &u64) -> bool {
+            *account != NOT_MEMBER
+        }
     }
 
     type System = system::Module<Test>;
@@ -629,6 +1992,7 @@ mod tests {
     const PROPOSER2: u64 = 12;
 
     const NOT_COUNCILOR: u64 = 22;
+    const NOT_MEMBER: u64 = 33;
 
     const ALL_COUNCILORS: [u64; 5] = [
         COUNCILOR1,
@@ -665,6 +2029,13 @@ mod tests {
         t.extend(council::GenesisConfig::<Test>{
             active_council: council_mock,
             term_ends_at: 0,
+            min_participation_percent: 0,
+            min_eligible_proposals: 5,
+            inactivity_penalty: council::InactivityPenalty::NoPenalty,
+            reward_per_block: 0,
+            stake_bonus_per_block: 0,
+            reward_payout_interval: 600,
+            reward_treasury: None,
         }.build_storage().unwrap().0);
 
         // t.extend(GenesisConfig::<Test>{
@@ -703,7 +2074,14 @@ mod tests {
     }
 
     fn wasm_code() -> Vec<u8> {
-        b"Proposal Wasm Code".to_vec()
+        let spec_name = crate::VERSION.spec_name.as_bytes();
+        let spec_version = crate::VERSION.spec_version + 1;
+        runtime_code::test_module::build(&runtime_code::REQUIRED_EXPORTS, Some((spec_name, spec_version)))
+    }
+
+    /// Storage deposit of a default proposal.
+    fn default_deposit() -> u64 {
+        (name().len() + description().len() + wasm_code().len()) as u64 * Proposals::deposit_per_byte()
     }
 
     fn _create_default_proposal() -> Result {
@@ -726,6 +2104,35 @@ mod tests {
         )
     }
 
+    fn _create_proposal_of_kind(
+        origin: Option<u64>,
+        kind: ProposalKind<u64, u64, u64, H256>
+    ) -> Result {
+        Proposals::create_proposal_of_kind(
+            Origin::signed(origin.unwrap_or(PROPOSER1)),
+            min_stake(),
+            self::name(),
+            self::description(),
+            kind
+        )
+    }
+
+    /// All councilors vote on a proposal in the same way and the tally is finalized.
+    fn _all_councilors_vote_and_finalize(proposal_id: u32, vote: VoteKind) {
+        for &councilor in ALL_COUNCILORS.iter() {
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(councilor), proposal_id, vote.clone()));
+        }
+        System::set_block_number(2);
+        Proposals::on_finalise(2);
+    }
+
+    /// Let the grace period pass for proposals approved at a given block.
+    fn _wait_for_grace_period(approved_at: u64) {
+        let execution_block = approved_at + Proposals::grace_period();
+        System::set_block_number(execution_block);
+        Proposals::on_finalise(execution_block);
+    }
+
     fn get_runtime_code() -> Option<Vec<u8>> {
         storage::unhashed::get_raw(well_known_keys::CODE)
     }
@@ -764,21 +2171,26 @@ mod tests {
             assert_eq!(Proposals::active_proposal_ids()[0], 1);
 
             let wasm_hash = BlakeTwo256::hash(&wasm_code());
-            let expected_proposal = RuntimeUpgradeProposal {
+            let expected_proposal = Proposal {
                 id: 1,
                 proposer: PROPOSER1,
                 stake: min_stake(),
+                deposit: default_deposit(),
                 name: name(),
                 description: description(),
-                wasm_hash,
+                kind: ProposalKind::RuntimeUpgrade(wasm_hash),
                 proposed_at: 1,
-                status: Active
+                expires_at: 1 + Proposals::voting_period(),
+                status: Active,
+                superseded_by: None
             };
             assert_eq!(Proposals::proposals(1), expected_proposal);
+            assert_eq!(Proposals::wasm_code_by_hash(wasm_hash), wasm_code());
 
-            // Check that stake amount has been locked on proposer's balance:
-            assert_eq!(Balances::free_balance(PROPOSER1), initial_balance() - min_stake());
-            assert_eq!(Balances::reserved_balance(PROPOSER1), min_stake());
+            // Check that stake amount and deposit have been locked on proposer's balance:
+            let locked = min_stake() + default_deposit();
+            assert_eq!(Balances::free_balance(PROPOSER1), initial_balance() - locked);
+            assert_eq!(Balances::reserved_balance(PROPOSER1), locked);
 
             // TODO expect event ProposalCreated(AccountId, u32)
         });
@@ -787,9 +2199,9 @@ mod tests {
     #[test]
     fn not_member_cannot_create_proposal() {
         with_externalities(&mut new_test_ext(), || {
-            // In this test a proposer has an empty balance
-            // thus he is not considered as a member.
-            assert_eq!(_create_default_proposal(),
+            Balances::set_free_balance(&NOT_MEMBER, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_eq!(_create_proposal(Some(NOT_MEMBER), None, None, None, None),
                 Err(MSG_ONLY_MEMBERS_CAN_PROPOSE));
         });
     }
@@ -810,6 +2222,43 @@ mod tests {
         });
     }
 
+    #[test]
+    fn cannot_create_proposal_when_balance_cannot_cover_deposit() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            // Balance is enough for the stake, but not for the stake and the deposit:
+            let stake = initial_balance() - default_deposit() + 1;
+            assert_eq!(_create_proposal(None, Some(stake), None, None, None),
+                Err(MSG_STAKE_IS_GREATER_THAN_BALANCE));
+
+            assert_ok!(_create_proposal(None, Some(stake - 1), None, None, None));
+            assert_eq!(Balances::free_balance(PROPOSER1), 0);
+        });
+    }
+
+    #[test]
+    fn deposit_is_proportional_to_proposal_size() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 2);
+            Balances::increase_total_stake_by(initial_balance() * 2);
+
+            assert_ok!(Proposals::set_deposit_per_byte(Origin::ROOT, 2));
+            let big_description = vec![65; 50];
+            assert_ok!(_create_proposal(None, None, None, Some(big_description.clone()), None));
+
+            let expected_deposit = 2 * (name().len() + big_description.len() + wasm_code().len()) as u64;
+            assert_eq!(Proposals::proposals(1).deposit, expected_deposit);
+            assert_eq!(Balances::reserved_balance(PROPOSER1), min_stake() + expected_deposit);
+
+            // Deposit is returned in full on rejection, unlike the stake:
+            _all_councilors_vote_and_finalize(1, Reject);
+            assert_eq!(Balances::free_balance(PROPOSER1), initial_balance() * 2 - rejection_fee());
+            assert_eq!(Balances::reserved_balance(PROPOSER1), 0);
+        });
+    }
+
     #[test]
     fn cannot_create_proposal_when_stake_is_greater_than_balance() {
         with_externalities(&mut new_test_ext(), || {
@@ -872,16 +2321,839 @@ mod tests {
         });
     }
 
+    #[test]
+    fn cannot_create_proposal_with_invalid_wasm_code() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            let spec_name = crate::VERSION.spec_name.as_bytes();
+            let spec_version = crate::VERSION.spec_version;
+            let exports = runtime_code::REQUIRED_EXPORTS;
+
+            assert_eq!(_create_proposal(
+                None, None, None, None, Some(b"Proposal Wasm Code".to_vec())),
+                Err(runtime_code::MSG_WASM_INVALID_HEADER));
+
+            assert_eq!(_create_proposal(
+                None, None, None, None, Some(runtime_code::test_module::build(&exports[..1], None))),
+                Err(runtime_code::MSG_WASM_MISSING_EXPORT));
+
+            assert_eq!(_create_proposal(
+                None, None, None, None, Some(runtime_code::test_module::build(&exports, None))),
+                Err(runtime_code::MSG_WASM_MISSING_RUNTIME_VERSION));
+
+            assert_eq!(_create_proposal(
+                None, None, None, None, Some(runtime_code::test_module::build(&exports, Some((&b"other"[..], spec_version + 1))))),
+                Err(runtime_code::MSG_WASM_SPEC_NAME_MISMATCH));
+
+            assert_eq!(_create_proposal(
+                None, None, None, None, Some(runtime_code::test_module::build(&exports, Some((spec_name, spec_version))))),
+                Err(runtime_code::MSG_WASM_SPEC_VERSION_NOT_HIGHER));
+
+            assert_ok!(_create_proposal(
+                None, None, None, None, Some(runtime_code::test_module::build(&exports, Some((spec_name, spec_version + 1))))));
+        });
+    }
+
     fn too_long_name() -> Vec<u8> {
         vec![65; Proposals::name_max_len() as usize + 1]
     }
 
-    fn too_long_description() -> Vec<u8> {
-        vec![65; Proposals::description_max_len() as usize + 1]
-    }
+    fn too_long_description() -> Vec<u8> {
+        vec![65; Proposals::description_max_len() as usize + 1]
+    }
+
+    fn too_long_wasm_code() -> Vec<u8> {
+        vec![65; Proposals::wasm_code_max_len() as usize + 1]
+    }
+
+    // -------------------------------------------------------------------
+    // Proposal kinds
+
+    #[test]
+    fn member_create_text_proposal() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            assert_ok!(_create_proposal_of_kind(None, ProposalKind::Text));
+            assert_eq!(Proposals::proposals(1).kind, ProposalKind::Text);
+            let deposit = (name().len() + description().len()) as u64;
+            assert_eq!(Balances::reserved_balance(PROPOSER1), min_stake() + deposit);
+
+            _all_councilors_vote_and_finalize(1, Approve);
+            _wait_for_grace_period(2);
+
+            // Nothing is executed for a text proposal, but it gets approved:
+            assert_runtime_code_empty!();
+            assert_eq!(Proposals::proposals(1).status, Approved);
+            assert_eq!(Balances::free_balance(PROPOSER1), initial_balance());
+        });
+    }
+
+    #[test]
+    fn cannot_create_runtime_upgrade_of_kind_with_unknown_wasm_hash() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 2);
+            Balances::increase_total_stake_by(initial_balance() * 2);
+
+            let wasm_hash = BlakeTwo256::hash(&wasm_code());
+            assert_eq!(_create_proposal_of_kind(None, ProposalKind::RuntimeUpgrade(wasm_hash)),
+                Err(MSG_WASM_CODE_NOT_FOUND));
+
+            // Once the code is uploaded, other proposals can refer to it by hash:
+            assert_ok!(_create_default_proposal());
+            assert_ok!(_create_proposal_of_kind(None, ProposalKind::RuntimeUpgrade(wasm_hash)));
+            assert_eq!(Proposals::active_proposal_ids(), vec![1, 2]);
+            assert_eq!(Proposals::wasm_code_ref_count(wasm_hash), 2);
+        });
+    }
+
+    #[test]
+    fn wasm_code_is_removed_when_no_proposal_refers_to_it() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 2);
+            Balances::increase_total_stake_by(initial_balance() * 2);
+
+            let wasm_hash = BlakeTwo256::hash(&wasm_code());
+            assert_ok!(_create_default_proposal());
+            assert_ok!(_create_proposal_of_kind(None, ProposalKind::RuntimeUpgrade(wasm_hash)));
+
+            // The code is still needed by the second proposal:
+            assert_ok!(Proposals::cancel_proposal(Origin::signed(PROPOSER1), 1));
+            assert_eq!(Proposals::wasm_code_ref_count(wasm_hash), 1);
+            assert_eq!(Proposals::wasm_code_by_hash(wasm_hash), wasm_code());
+
+            _all_councilors_vote_and_finalize(2, Reject);
+            assert_eq!(Proposals::proposals(2).status, Rejected);
+            assert!(!<WasmCodeByHash<Test>>::exists(wasm_hash));
+            assert!(!<WasmCodeRefCount<Test>>::exists(wasm_hash));
+        });
+    }
+
+    #[test]
+    fn wasm_code_is_removed_after_proposal_is_executed() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            let wasm_hash = BlakeTwo256::hash(&wasm_code());
+            assert_ok!(_create_default_proposal());
+            _all_councilors_vote_and_finalize(1, Approve);
+
+            // Pending proposal still refers to the code:
+            assert_eq!(Proposals::wasm_code_by_hash(wasm_hash), wasm_code());
+
+            _wait_for_grace_period(2);
+            assert_runtime_code!(wasm_code());
+            assert!(!<WasmCodeByHash<Test>>::exists(wasm_hash));
+        });
+    }
+
+    #[test]
+    fn root_can_purge_only_unreferenced_wasm_code() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            let wasm_hash = BlakeTwo256::hash(&wasm_code());
+            assert_eq!(Proposals::purge_wasm_code(Origin::ROOT, wasm_hash), Err(MSG_WASM_CODE_NOT_FOUND));
+
+            assert_ok!(_create_default_proposal());
+            assert_eq!(Proposals::purge_wasm_code(Origin::ROOT, wasm_hash), Err(MSG_WASM_CODE_IS_IN_USE));
+
+            // Code that has been stored without a reference counter:
+            let stale_code = b"Stale Wasm Code".to_vec();
+            let stale_hash = BlakeTwo256::hash(&stale_code);
+            <WasmCodeByHash<Test>>::insert(stale_hash, stale_code);
+
+            assert_ok!(Proposals::purge_wasm_code(Origin::ROOT, stale_hash));
+            assert!(!<WasmCodeByHash<Test>>::exists(stale_hash));
+        });
+    }
+
+    #[test]
+    fn approved_parameter_change_proposal_updates_parameter() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            assert_eq!(_create_proposal_of_kind(None,
+                ProposalKind::ParameterChange(ProposalParameter::ApprovalQuorum(101))),
+                Err(MSG_INVALID_APPROVAL_QUORUM));
+            assert_eq!(_create_proposal_of_kind(None,
+                ProposalKind::ParameterChange(ProposalParameter::RejectionFee(min_stake() + 1))),
+                Err(MSG_FEE_IS_GREATER_THAN_MIN_STAKE));
+
+            assert_ok!(_create_proposal_of_kind(None,
+                ProposalKind::ParameterChange(ProposalParameter::ApprovalQuorum(80))));
+
+            _all_councilors_vote_and_finalize(1, Approve);
+            _wait_for_grace_period(2);
+
+            assert_eq!(Proposals::proposals(1).status, Approved);
+            assert_eq!(Proposals::approval_quorum(), 80);
+        });
+    }
+
+    #[test]
+    fn approved_treasury_spend_proposal_transfers_funds() {
+        with_externalities(&mut new_test_ext(), || {
+            const TREASURY: u64 = 100;
+            const BENEFICIARY: u64 = 101;
+
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::set_free_balance(&TREASURY, 1000);
+            Balances::set_free_balance(&BENEFICIARY, 1);
+            Balances::increase_total_stake_by(initial_balance() + 1001);
+            <TreasuryAccount<Test>>::put(TREASURY);
+
+            assert_eq!(_create_proposal_of_kind(None, ProposalKind::TreasurySpend(BENEFICIARY, 0)),
+                Err(MSG_ZERO_TREASURY_SPEND));
+            assert_ok!(_create_proposal_of_kind(None, ProposalKind::TreasurySpend(BENEFICIARY, 300)));
+
+            _all_councilors_vote_and_finalize(1, Approve);
+            _wait_for_grace_period(2);
+
+            assert_eq!(Proposals::proposals(1).status, Approved);
+            assert_eq!(Balances::free_balance(TREASURY), 700);
+            assert_eq!(Balances::reserved_balance(TREASURY), 0);
+            assert_eq!(Balances::free_balance(BENEFICIARY), 301);
+        });
+    }
+
+    #[test]
+    fn approved_council_membership_proposals_change_council() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            assert_eq!(_create_proposal_of_kind(None, ProposalKind::AddCouncilMember(COUNCILOR1)),
+                Err(MSG_ACCOUNT_IS_COUNCILOR));
+            assert_eq!(_create_proposal_of_kind(None, ProposalKind::RemoveCouncilMember(NOT_COUNCILOR)),
+                Err(MSG_ACCOUNT_IS_NOT_COUNCILOR));
+
+            assert_ok!(_create_proposal_of_kind(None, ProposalKind::RemoveCouncilMember(COUNCILOR5)));
+            _all_councilors_vote_and_finalize(1, Approve);
+            _wait_for_grace_period(2);
+
+            assert_eq!(Proposals::proposals(1).status, Approved);
+            assert!(!Proposals::is_councilor(&COUNCILOR5));
+            assert_eq!(Proposals::councilors_count(), ALL_COUNCILORS.len() as u32 - 1);
+        });
+    }
+
+    // -------------------------------------------------------------------
+    // Settings by proposal category
+
+    fn settings(quorum_percent: u32, approval_threshold_percent: u32) -> ProposalSettings<u64, u64> {
+        ProposalSettings {
+            quorum_percent,
+            approval_threshold_percent,
+            voting_period: 10,
+            min_stake: min_stake() * 2,
+            tally_mode: TallyMode::Count,
+        }
+    }
+
+    #[test]
+    fn categories_without_own_settings_use_global_parameters() {
+        with_externalities(&mut new_test_ext(), || {
+            let settings = Proposals::settings(ProposalCategory::Text);
+            assert_eq!(settings.quorum_percent, Proposals::approval_quorum());
+            assert_eq!(settings.approval_threshold_percent, Proposals::approval_threshold());
+            assert_eq!(settings.voting_period, Proposals::voting_period());
+            assert_eq!(settings.min_stake, Proposals::min_stake());
+
+            // The global threshold is off by default, so only the quorum is checked:
+            assert_eq!(Proposals::approval_threshold(), 0);
+            assert_eq!(Proposals::set_approval_threshold(Origin::ROOT, 101), Err(MSG_INVALID_APPROVAL_THRESHOLD));
+            assert_ok!(Proposals::set_approval_threshold(Origin::ROOT, 50));
+            assert_eq!(Proposals::settings(ProposalCategory::Text).approval_threshold_percent, 50);
+
+            assert_ok!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::Text, self::settings(50, 50)));
+            assert_eq!(Proposals::settings(ProposalCategory::Text), self::settings(50, 50));
+            assert_eq!(Proposals::settings(ProposalCategory::RuntimeUpgrade).quorum_percent,
+                Proposals::approval_quorum());
+
+            assert_ok!(Proposals::reset_proposal_settings(Origin::ROOT, ProposalCategory::Text));
+            assert_eq!(Proposals::settings(ProposalCategory::Text).min_stake, Proposals::min_stake());
+        });
+    }
+
+    #[test]
+    fn cannot_set_invalid_proposal_settings() {
+        with_externalities(&mut new_test_ext(), || {
+            assert_eq!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::Text, settings(0, 50)),
+                Err(MSG_INVALID_APPROVAL_QUORUM));
+            assert_eq!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::Text, settings(50, 101)),
+                Err(MSG_INVALID_APPROVAL_THRESHOLD));
+
+            let mut zero_period = settings(50, 50);
+            zero_period.voting_period = 0;
+            assert_eq!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::Text, zero_period),
+                Err(MSG_ZERO_VOTING_PERIOD));
+
+            let mut low_stake = settings(50, 50);
+            low_stake.min_stake = rejection_fee() - 1;
+            assert_eq!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::Text, low_stake),
+                Err(MSG_FEE_IS_GREATER_THAN_MIN_STAKE));
+        });
+    }
+
+    #[test]
+    fn min_stake_depends_on_proposal_category() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            assert_ok!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::Text, settings(50, 50)));
+
+            // The default min stake is not enough for a text proposal anymore:
+            assert_eq!(_create_proposal_of_kind(None, ProposalKind::Text), Err(MSG_STAKE_IS_TOO_LOW));
+
+            // But it is still enough for a runtime upgrade:
+            assert_ok!(_create_default_proposal());
+        });
+    }
+
+    #[test]
+    fn reject_proposal_when_category_quorum_not_reached() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            // 3 of 5 councilors would pass the default quorum, but not 80%:
+            assert_ok!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::RuntimeUpgrade, ProposalSettings {
+                quorum_percent: 80,
+                approval_threshold_percent: 50,
+                voting_period: Proposals::voting_period(),
+                min_stake: min_stake(),
+                tally_mode: TallyMode::Count,
+            }));
+            assert_ok!(_create_default_proposal());
+
+            for (i, &councilor) in ALL_COUNCILORS.iter().enumerate() {
+                let vote = if i < 3 { Approve } else { Reject };
+                assert_ok!(Proposals::vote_on_proposal(Origin::signed(councilor), 1, vote));
+            }
+
+            System::set_block_number(2);
+            Proposals::on_finalise(2);
+
+            assert_runtime_code_empty!();
+            assert_eq!(Proposals::proposals(1).status, Rejected);
+        });
+    }
+
+    #[test]
+    fn reject_proposal_when_approval_threshold_not_reached() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 2);
+            Balances::increase_total_stake_by(initial_balance() * 2);
+
+            // A single approval clears the quorum, but 2 of 5 is less than 50% of votes:
+            assert_ok!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::Text, settings(20, 50)));
+            assert_ok!(Proposals::create_proposal_of_kind(
+                Origin::signed(PROPOSER1), min_stake() * 2, name(), description(), ProposalKind::Text));
+
+            for (i, &councilor) in ALL_COUNCILORS.iter().enumerate() {
+                let vote = if i < 2 { Approve } else { Reject };
+                assert_ok!(Proposals::vote_on_proposal(Origin::signed(councilor), 1, vote));
+            }
+
+            System::set_block_number(2);
+            Proposals::on_finalise(2);
+
+            assert_eq!(Proposals::proposals(1).status, Rejected);
+            assert_eq!(Proposals::tally_results(1).approvals, 2);
+        });
+    }
+
+    fn stake_weighted_settings(quorum_percent: u32, approval_threshold_percent: u32) -> ProposalSettings<u64, u64> {
+        ProposalSettings {
+            tally_mode: TallyMode::StakeWeighted,
+            ..settings(quorum_percent, approval_threshold_percent)
+        }
+    }
+
+    /// Councilor 1 has a seat stake of 300, councilors 2 and 3 - 100, councilors 4 and 5 - 50.
+    fn elect_staked_council() {
+        let seats: council::Seats<u64, u64> = ALL_COUNCILORS.iter().enumerate().map(|(i, &member)| {
+            let (stake, backers) = match i {
+                0 => (100, vec![council::election::Backer { member: MEMBER1, stake: 200 }]),
+                1 | 2 => (100, vec![]),
+                _ => (50, vec![]),
+            };
+            council::Seat { member, stake, backers }
+        }).collect();
+        <council::Module<Test> as council::CouncilElected<_, _>>::council_elected(seats, 100);
+    }
+
+    #[test]
+    fn stake_weighted_tally_counts_seat_stakes() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 2);
+            Balances::increase_total_stake_by(initial_balance() * 2);
+            elect_staked_council();
+
+            assert_ok!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::Text, stake_weighted_settings(50, 50)));
+            assert_ok!(Proposals::create_proposal_of_kind(
+                Origin::signed(PROPOSER1), min_stake() * 2, name(), description(), ProposalKind::Text));
+
+            // Only 1 of 5 councilors approves, but it has a half of the council stake:
+            for (i, &councilor) in ALL_COUNCILORS.iter().enumerate() {
+                let vote = if i == 0 { Approve } else { Reject };
+                assert_ok!(Proposals::vote_on_proposal(Origin::signed(councilor), 1, vote));
+            }
+
+            System::set_block_number(2);
+            Proposals::on_finalise(2);
+
+            let tally_result = Proposals::tally_results(1);
+            assert_eq!(tally_result.status, Approved);
+            assert_eq!(tally_result.approvals, 1);
+            assert_eq!(tally_result.weighted, Some(VoteStakes {
+                abstentions: 0,
+                approvals: 300,
+                rejections: 300,
+                slashes: 0,
+                council_stake: 600,
+            }));
+        });
+    }
+
+    #[test]
+    fn stake_weighted_tally_rejects_when_stake_quorum_not_reached() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 2);
+            Balances::increase_total_stake_by(initial_balance() * 2);
+            elect_staked_council();
+
+            assert_ok!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::Text, stake_weighted_settings(50, 50)));
+            assert_ok!(Proposals::create_proposal_of_kind(
+                Origin::signed(PROPOSER1), min_stake() * 2, name(), description(), ProposalKind::Text));
+
+            // 3 of 5 councilors approve, but they have less than a half of the council stake:
+            for (i, &councilor) in ALL_COUNCILORS.iter().enumerate() {
+                let vote = if i == 0 || i == 1 { Reject } else { Approve };
+                assert_ok!(Proposals::vote_on_proposal(Origin::signed(councilor), 1, vote));
+            }
+
+            System::set_block_number(2);
+            Proposals::on_finalise(2);
+
+            assert_eq!(Proposals::proposals(1).status, Rejected);
+            assert_eq!(Proposals::tally_results(1).weighted.unwrap().approvals, 200);
+        });
+    }
+
+    #[test]
+    fn stake_weighted_tally_counts_votes_of_council_without_stakes() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 2);
+            Balances::increase_total_stake_by(initial_balance() * 2);
+
+            assert_ok!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::Text, stake_weighted_settings(60, 50)));
+            assert_ok!(Proposals::create_proposal_of_kind(
+                Origin::signed(PROPOSER1), min_stake() * 2, name(), description(), ProposalKind::Text));
+
+            for (i, &councilor) in ALL_COUNCILORS.iter().enumerate() {
+                let vote = if i < 3 { Approve } else { Reject };
+                assert_ok!(Proposals::vote_on_proposal(Origin::signed(councilor), 1, vote));
+            }
+
+            System::set_block_number(2);
+            Proposals::on_finalise(2);
+
+            assert_eq!(Proposals::tally_results(1).status, Approved);
+        });
+    }
+
+    #[test]
+    fn proposal_expires_after_category_voting_period() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 2);
+            Balances::increase_total_stake_by(initial_balance() * 2);
+
+            assert_ok!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::Text, settings(50, 50)));
+            assert_ok!(Proposals::create_proposal_of_kind(
+                Origin::signed(PROPOSER1), min_stake() * 2, name(), description(), ProposalKind::Text));
+
+            let expiration_block = System::block_number() + 10;
+            System::set_block_number(expiration_block);
+            Proposals::on_finalise(expiration_block);
+
+            assert_eq!(Proposals::proposals(1).status, Expired);
+            assert_eq!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR1), 1, Approve),
+                Err(MSG_PROPOSAL_FINALIZED));
+        });
+    }
+
+    // -------------------------------------------------------------------
+    // Superseding
+
+    fn revised_wasm_code() -> Vec<u8> {
+        let spec_name = crate::VERSION.spec_name.as_bytes();
+        let spec_version = crate::VERSION.spec_version + 2;
+        runtime_code::test_module::build(&runtime_code::REQUIRED_EXPORTS, Some((spec_name, spec_version)))
+    }
+
+    #[test]
+    fn proposer_can_supersede_active_proposal() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 2);
+            Balances::increase_total_stake_by(initial_balance() * 2);
+            assert_ok!(_create_default_proposal());
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR1), 1, Reject));
+
+            let revised_description = b"Revised Description".to_vec();
+            assert_ok!(Proposals::supersede_proposal(
+                Origin::signed(PROPOSER1), 1, name(), revised_description.clone(), revised_wasm_code()));
+
+            let old_proposal = Proposals::proposals(1);
+            assert_eq!(old_proposal.status, Superseded);
+            assert_eq!(old_proposal.superseded_by, Some(2));
+
+            let new_proposal = Proposals::proposals(2);
+            assert_eq!(new_proposal.status, Active);
+            assert_eq!(new_proposal.stake, min_stake());
+            assert_eq!(new_proposal.description, revised_description);
+            assert_eq!(Proposals::active_proposal_ids(), vec![2]);
+
+            // Votes start over for the new proposal:
+            assert!(Proposals::votes_by_proposal(2).is_empty());
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR1), 2, Approve));
+
+            // No cancellation fee is taken, the stake is kept, and the old deposit is returned:
+            let new_deposit = new_proposal.deposit;
+            assert_eq!(Balances::reserved_balance(PROPOSER1), min_stake() + new_deposit);
+            assert_eq!(Balances::free_balance(PROPOSER1), initial_balance() * 2 - min_stake() - new_deposit);
+
+            // Code of the superseded proposal is not needed anymore:
+            assert!(!<WasmCodeByHash<Test>>::exists(BlakeTwo256::hash(&wasm_code())));
+        });
+    }
+
+    #[test]
+    fn cannot_supersede_proposal_of_other_proposer_or_finalized_one() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 2);
+            Balances::set_free_balance(&PROPOSER2, initial_balance());
+            Balances::increase_total_stake_by(initial_balance() * 3);
+            assert_ok!(_create_default_proposal());
+
+            assert_eq!(Proposals::supersede_proposal_of_kind(
+                Origin::signed(PROPOSER2), 1, name(), description(), ProposalKind::Text),
+                Err(MSG_YOU_DONT_OWN_THIS_PROPOSAL));
+
+            assert_ok!(Proposals::supersede_proposal_of_kind(
+                Origin::signed(PROPOSER1), 1, name(), description(), ProposalKind::Text));
+            assert_eq!(Proposals::supersede_proposal_of_kind(
+                Origin::signed(PROPOSER1), 1, name(), description(), ProposalKind::Text),
+                Err(MSG_PROPOSAL_FINALIZED));
+            assert_eq!(Proposals::cancel_proposal(Origin::signed(PROPOSER1), 1),
+                Err(MSG_PROPOSAL_FINALIZED));
+        });
+    }
+
+    // -------------------------------------------------------------------
+    // Discussion
+
+    #[test]
+    fn members_and_councilors_can_discuss_active_proposal() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            assert_ok!(Proposals::add_discussion_post(Origin::signed(PROPOSER1), 1, b"First".to_vec()));
+            assert_ok!(Proposals::add_discussion_post(Origin::signed(COUNCILOR1), 1, b"Second".to_vec()));
+            assert_eq!(Proposals::add_discussion_post(Origin::signed(NOT_MEMBER), 1, b"Third".to_vec()),
+                Err(MSG_ONLY_MEMBERS_CAN_POST));
+
+            assert_eq!(Proposals::posts_by_proposal(1), vec![
+                DiscussionPost { author: PROPOSER1, text: b"First".to_vec(), posted_at: 1 },
+                DiscussionPost { author: COUNCILOR1, text: b"Second".to_vec(), posted_at: 1 },
+            ]);
+        });
+    }
+
+    #[test]
+    fn cannot_post_invalid_or_too_many_posts() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            assert_eq!(Proposals::add_discussion_post(Origin::signed(PROPOSER1), 2, b"Post".to_vec()),
+                Err(MSG_PROPOSAL_NOT_FOUND));
+            assert_eq!(Proposals::add_discussion_post(Origin::signed(PROPOSER1), 1, vec![]),
+                Err(MSG_EMPTY_POST_PROVIDED));
+            let too_long_post = vec![65; Proposals::post_max_len() as usize + 1];
+            assert_eq!(Proposals::add_discussion_post(Origin::signed(PROPOSER1), 1, too_long_post),
+                Err(MSG_TOO_LONG_POST));
+
+            <MaxPostsPerProposal<Test>>::put(1);
+            assert_ok!(Proposals::add_discussion_post(Origin::signed(PROPOSER1), 1, b"Post".to_vec()));
+            assert_eq!(Proposals::add_discussion_post(Origin::signed(PROPOSER1), 1, b"Post".to_vec()),
+                Err(MSG_TOO_MANY_POSTS));
+        });
+    }
+
+    #[test]
+    fn discussion_is_locked_after_finalization() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            _all_councilors_vote_and_finalize(1, Reject);
+            assert_eq!(Proposals::add_discussion_post(Origin::signed(COUNCILOR1), 1, b"Late".to_vec()),
+                Err(MSG_PROPOSAL_FINALIZED));
+            assert_eq!(Proposals::amend_description(Origin::signed(PROPOSER1), 1, b"Late".to_vec()),
+                Err(MSG_PROPOSAL_FINALIZED));
+        });
+    }
+
+    #[test]
+    fn proposer_can_amend_description_once() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            let new_description = b"Amended Description".to_vec();
+            assert_eq!(Proposals::amend_description(Origin::signed(PROPOSER2), 1, new_description.clone()),
+                Err(MSG_YOU_DONT_OWN_THIS_PROPOSAL));
+
+            assert_ok!(Proposals::amend_description(Origin::signed(PROPOSER1), 1, new_description.clone()));
+            assert_eq!(Proposals::proposals(1).description, new_description);
+            assert_eq!(Proposals::description_history(1), vec![description()]);
+
+            // The new description is paid with an extra deposit:
+            let deposit = default_deposit() + new_description.len() as u64;
+            assert_eq!(Proposals::proposals(1).deposit, deposit);
+            assert_eq!(Balances::reserved_balance(PROPOSER1), min_stake() + deposit);
+
+            assert_eq!(Proposals::amend_description(Origin::signed(PROPOSER1), 1, description()),
+                Err(MSG_DESCRIPTION_ALREADY_AMENDED));
+        });
+    }
+
+    // -------------------------------------------------------------------
+    // Advisory votes
+
+    const MEMBER1: u64 = 41;
+    const MEMBER2: u64 = 42;
+    const MEMBER3: u64 = 43;
+    const MEMBER4: u64 = 44;
+
+    #[test]
+    fn members_can_cast_advisory_votes() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            assert_eq!(Proposals::cast_advisory_vote(Origin::signed(COUNCILOR1), 1, Approve),
+                Err(MSG_COUNCILORS_CANNOT_SIGNAL));
+            assert_eq!(Proposals::cast_advisory_vote(Origin::signed(NOT_MEMBER), 1, Approve),
+                Err(MSG_ONLY_MEMBERS_CAN_SIGNAL));
+
+            assert_ok!(Proposals::cast_advisory_vote(Origin::signed(MEMBER1), 1, Approve));
+            assert_ok!(Proposals::cast_advisory_vote(Origin::signed(MEMBER1), 1, Reject));
+            assert_eq!(Proposals::advisory_votes_by_proposal(1), vec![(MEMBER1, Reject)]);
+
+            _all_councilors_vote_and_finalize(1, Approve);
+
+            assert_eq!(Proposals::cast_advisory_vote(Origin::signed(MEMBER2), 1, Approve),
+                Err(MSG_PROPOSAL_FINALIZED));
+        });
+    }
+
+    #[test]
+    fn advisory_tally_follows_delegations() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            Balances::set_free_balance(&MEMBER1, 100);
+            Balances::set_free_balance(&MEMBER2, 200);
+            Balances::set_free_balance(&MEMBER3, 300);
+            Balances::set_free_balance(&MEMBER4, 400);
+
+            // MEMBER3 -> MEMBER2 -> COUNCILOR1
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER2), COUNCILOR1));
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER3), MEMBER2));
+            // MEMBER4 -> MEMBER1, but MEMBER4 votes directly.
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER4), MEMBER1));
+
+            assert_ok!(Proposals::cast_advisory_vote(Origin::signed(MEMBER1), 1, Reject));
+            assert_ok!(Proposals::cast_advisory_vote(Origin::signed(MEMBER4), 1, Abstain));
+
+            _all_councilors_vote_and_finalize(1, Approve);
+
+            // Councilors have no balance in this test.
+            assert_eq!(Proposals::advisory_tally_results(1), AdvisoryTally {
+                abstentions: 400,
+                approvals: 500,
+                rejections: 100,
+                slashes: 0,
+                voters: ALL_COUNCILORS.len() as u32 + 4,
+            });
+        });
+    }
+
+    #[test]
+    fn advisory_tally_respects_max_delegation_depth() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            Balances::set_free_balance(&MEMBER1, 100);
+            Balances::set_free_balance(&MEMBER2, 200);
+
+            // MEMBER2 -> MEMBER1 -> COUNCILOR1
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER1), COUNCILOR1));
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER2), MEMBER1));
+            assert_ok!(Proposals::set_max_delegation_depth(Origin::ROOT, 1));
+
+            _all_councilors_vote_and_finalize(1, Approve);
+
+            assert_eq!(Proposals::advisory_tally_results(1).approvals, 100);
+        });
+    }
+
+    #[test]
+    fn delegation_rules_are_enforced() {
+        with_externalities(&mut new_test_ext(), || {
+            assert_eq!(Proposals::delegate_signal(Origin::signed(COUNCILOR1), COUNCILOR2),
+                Err(MSG_COUNCILORS_CANNOT_SIGNAL));
+            assert_eq!(Proposals::delegate_signal(Origin::signed(NOT_MEMBER), COUNCILOR1),
+                Err(MSG_ONLY_MEMBERS_CAN_SIGNAL));
+            assert_eq!(Proposals::delegate_signal(Origin::signed(MEMBER1), MEMBER1),
+                Err(MSG_CANNOT_DELEGATE_TO_SELF));
+            assert_eq!(Proposals::delegate_signal(Origin::signed(MEMBER1), NOT_MEMBER),
+                Err(MSG_DELEGATE_IS_NOT_MEMBER));
+
+            // MEMBER1 -> MEMBER2 -> MEMBER3
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER1), MEMBER2));
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER2), MEMBER3));
+            assert_eq!(Proposals::delegate_signal(Origin::signed(MEMBER3), MEMBER1),
+                Err(MSG_DELEGATION_CYCLE));
+
+            assert_ok!(Proposals::set_max_delegation_depth(Origin::ROOT, 2));
+            assert_eq!(Proposals::delegate_signal(Origin::signed(MEMBER4), MEMBER1),
+                Err(MSG_DELEGATION_TOO_DEEP));
+
+            // Delegating again replaces the previous delegation:
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER1), MEMBER3));
+            assert_eq!(Proposals::signal_delegate_of(MEMBER1), Some(MEMBER3));
+            assert_eq!(Proposals::signal_delegators(MEMBER2), vec![]);
+            assert_eq!(Proposals::signal_delegators(MEMBER3), vec![MEMBER2, MEMBER1]);
+
+            assert_ok!(Proposals::undelegate_signal(Origin::signed(MEMBER1)));
+            assert_eq!(Proposals::signal_delegate_of(MEMBER1), None);
+            assert_eq!(Proposals::undelegate_signal(Origin::signed(MEMBER1)),
+                Err(MSG_NO_DELEGATION));
+        });
+    }
+
+    #[test]
+    fn delegation_depth_includes_delegators_of_sender() {
+        with_externalities(&mut new_test_ext(), || {
+            assert_ok!(Proposals::set_max_delegation_depth(Origin::ROOT, 2));
+
+            // MEMBER2 -> MEMBER1 -> MEMBER3
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER2), MEMBER1));
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER1), MEMBER3));
+            assert_eq!(Proposals::signal_delegation_height(MEMBER3), 2);
+
+            // MEMBER2 would be three delegations away from MEMBER4:
+            assert_eq!(Proposals::delegate_signal(Origin::signed(MEMBER3), MEMBER4),
+                Err(MSG_DELEGATION_TOO_DEEP));
+
+            assert_ok!(Proposals::undelegate_signal(Origin::signed(MEMBER2)));
+            assert_eq!(Proposals::signal_delegation_height(MEMBER1), 0);
+            assert_eq!(Proposals::signal_delegation_height(MEMBER3), 1);
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER3), MEMBER4));
+            assert_eq!(Proposals::signal_delegation_height(MEMBER4), 2);
+        });
+    }
+
+    #[test]
+    fn advisory_votes_and_delegators_are_limited() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            assert_ok!(Proposals::set_max_advisory_votes_per_proposal(Origin::ROOT, 1));
+            assert_ok!(Proposals::cast_advisory_vote(Origin::signed(MEMBER1), 1, Approve));
+            // A member who has voted already can change the vote:
+            assert_ok!(Proposals::cast_advisory_vote(Origin::signed(MEMBER1), 1, Reject));
+            assert_eq!(Proposals::cast_advisory_vote(Origin::signed(MEMBER2), 1, Approve),
+                Err(MSG_TOO_MANY_ADVISORY_VOTES));
+
+            assert_ok!(Proposals::set_max_followers_per_delegate(Origin::ROOT, 1));
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER2), MEMBER1));
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER2), MEMBER1));
+            assert_eq!(Proposals::delegate_signal(Origin::signed(MEMBER3), MEMBER1),
+                Err(MSG_TOO_MANY_FOLLOWERS));
+        });
+    }
+
+    #[test]
+    fn indirect_followers_count_towards_max_followers() {
+        with_externalities(&mut new_test_ext(), || {
+            assert_ok!(Proposals::set_max_followers_per_delegate(Origin::ROOT, 2));
+
+            // MEMBER3 -> MEMBER2 -> MEMBER1
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER2), MEMBER1));
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER3), MEMBER2));
+            assert_eq!(Proposals::signal_follower_count(MEMBER1), 2);
+            assert_eq!(Proposals::signal_follower_count(MEMBER2), 1);
+
+            // MEMBER1 has one direct delegator, but MEMBER4 would be its third follower:
+            assert_eq!(Proposals::delegate_signal(Origin::signed(MEMBER4), MEMBER1),
+                Err(MSG_TOO_MANY_FOLLOWERS));
+            assert_eq!(Proposals::delegate_signal(Origin::signed(MEMBER4), MEMBER3),
+                Err(MSG_TOO_MANY_FOLLOWERS));
+
+            // Moving within the same chain does not add followers to MEMBER1:
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER3), MEMBER1));
+            assert_eq!(Proposals::signal_follower_count(MEMBER1), 2);
+            assert_eq!(Proposals::signal_follower_count(MEMBER2), 0);
+
+            assert_ok!(Proposals::undelegate_signal(Origin::signed(MEMBER2)));
+            assert_eq!(Proposals::signal_follower_count(MEMBER1), 1);
+            assert_ok!(Proposals::delegate_signal(Origin::signed(MEMBER4), MEMBER1));
+            assert_eq!(Proposals::signal_follower_count(MEMBER1), 2);
+        });
+    }
+
+    // -------------------------------------------------------------------
+    // Councilor participation
 
-    fn too_long_wasm_code() -> Vec<u8> {
-        vec![65; Proposals::wasm_code_max_len() as usize + 1]
+    #[test]
+    fn finalized_proposals_are_counted_in_councilor_participation() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 2);
+            Balances::increase_total_stake_by(initial_balance() * 2);
+            assert_ok!(_create_default_proposal());
+            assert_ok!(_create_proposal(None, None, Some(b"Other".to_vec()), None, None));
+
+            // The second proposal stays active, so it is not counted yet:
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR1), 2, Approve));
+            _all_councilors_vote_and_finalize(1, Approve);
+
+            let participation = <council::Module<Test>>::participation_of(COUNCILOR1);
+            assert_eq!(participation, council::Participation { eligible: 1, voted: 1 });
+        });
     }
 
     // -------------------------------------------------------------------
@@ -977,6 +3249,115 @@ mod tests {
         });
     }
 
+    #[test]
+    fn councilor_change_vote_on_proposal() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            assert_eq!(Proposals::change_vote(Origin::signed(COUNCILOR1), 1, Reject),
+                Err(MSG_YOU_DID_NOT_VOTE));
+
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR1), 1, Approve));
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR2), 1, Approve));
+            assert_ok!(Proposals::change_vote(Origin::signed(COUNCILOR1), 1, Reject));
+
+            assert_eq!(Proposals::votes_by_proposal(1), vec![(COUNCILOR1, Reject), (COUNCILOR2, Approve)]);
+            assert_eq!(Proposals::vote_by_account_and_proposal((COUNCILOR1, 1)), Reject);
+        });
+    }
+
+    #[test]
+    fn councilor_retract_vote_on_proposal() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            assert_eq!(Proposals::retract_vote(Origin::signed(COUNCILOR1), 1),
+                Err(MSG_YOU_DID_NOT_VOTE));
+
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR1), 1, Approve));
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR2), 1, Approve));
+            assert_ok!(Proposals::retract_vote(Origin::signed(COUNCILOR1), 1));
+
+            assert_eq!(Proposals::votes_by_proposal(1), vec![(COUNCILOR2, Approve)]);
+            assert!(!<VoteByAccountAndProposal<Test>>::exists((COUNCILOR1, 1)));
+
+            // A retracted vote can be cast again:
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR1), 1, Slash));
+            assert_eq!(Proposals::votes_by_proposal(1), vec![(COUNCILOR2, Approve), (COUNCILOR1, Slash)]);
+        });
+    }
+
+    #[test]
+    fn vote_counts_follow_votes_on_proposal() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR1), 1, Approve));
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR2), 1, Reject));
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR3), 1, Abstain));
+            assert_ok!(Proposals::change_vote(Origin::signed(COUNCILOR2), 1, Slash));
+            assert_ok!(Proposals::retract_vote(Origin::signed(COUNCILOR3), 1));
+
+            assert_eq!(Proposals::vote_counts_by_proposal(1), VoteCounts {
+                abstentions: 0,
+                approvals: 1,
+                rejections: 0,
+                slashes: 1,
+            });
+            assert_eq!(Proposals::proposal_ids_to_tally(), vec![1]);
+
+            System::set_block_number(2);
+            Proposals::on_finalise(2);
+
+            // Not all councilors voted, so the proposal waits for its expiry block:
+            assert!(Proposals::proposal_ids_to_tally().is_empty());
+            assert_eq!(Proposals::proposals(1).status, Active);
+        });
+    }
+
+    #[test]
+    fn proposal_expiry_is_not_affected_by_later_voting_period_change() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            let expiration_block = 1 + Proposals::voting_period();
+            assert_eq!(Proposals::proposal_ids_by_expiry(expiration_block), vec![1]);
+
+            assert_ok!(Proposals::set_proposal_settings(Origin::ROOT, ProposalCategory::RuntimeUpgrade, settings(60, 50)));
+
+            System::set_block_number(expiration_block);
+            Proposals::on_finalise(expiration_block);
+
+            assert_eq!(Proposals::proposals(1).status, Expired);
+            assert!(Proposals::proposal_ids_by_expiry(expiration_block).is_empty());
+        });
+    }
+
+    #[test]
+    fn councilor_cannot_change_vote_on_finalized_proposal() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            _all_councilors_vote_and_finalize(1, Reject);
+            assert_eq!(Proposals::proposals(1).status, Rejected);
+
+            assert_eq!(Proposals::change_vote(Origin::signed(COUNCILOR1), 1, Approve),
+                Err(MSG_PROPOSAL_FINALIZED));
+            assert_eq!(Proposals::retract_vote(Origin::signed(COUNCILOR1), 1),
+                Err(MSG_PROPOSAL_FINALIZED));
+        });
+    }
+
     #[test]
     fn autovote_with_approve_when_councilor_creates_proposal() {
         with_externalities(&mut new_test_ext(), || {
@@ -1039,7 +3420,7 @@ mod tests {
             Proposals::on_finalise(2);
 
             assert!(Proposals::active_proposal_ids().is_empty());
-            assert_eq!(Proposals::proposals(1).status, Approved);
+            assert_eq!(Proposals::proposals(1).status, PendingExecution);
 
             // Try to vote on finalized proposal:
             assert_eq!(Proposals::vote_on_proposal(
@@ -1073,6 +3454,12 @@ mod tests {
             System::set_block_number(2);
             Proposals::on_finalise(2);
 
+            // Check that runtime code has NOT been updated until the grace period is over.
+            assert_runtime_code_empty!();
+            assert_eq!(Proposals::proposals(1).status, PendingExecution);
+
+            _wait_for_grace_period(2);
+
             // Check that runtime code has been updated after proposal approved.
             assert_runtime_code!(wasm_code());
 
@@ -1084,6 +3471,7 @@ mod tests {
                 approvals: ALL_COUNCILORS.len() as u32,
                 rejections: 0,
                 slashes: 0,
+                weighted: None,
                 status: Approved,
                 finalized_at: 2
             });
@@ -1096,6 +3484,31 @@ mod tests {
         });
     }
 
+    #[test]
+    fn votes_of_removed_councilors_are_not_counted_in_tally() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            assert_ok!(_create_default_proposal());
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR1), 1, Slash));
+            assert_ok!(<council::Module<Test>>::try_remove_council_member(COUNCILOR1));
+
+            for &councilor in ALL_COUNCILORS[1..].iter() {
+                assert_ok!(Proposals::vote_on_proposal(Origin::signed(councilor), 1, Approve));
+            }
+
+            // All of the current councilors have voted, so the proposal is finalized
+            // before its voting period is over:
+            System::set_block_number(2);
+            Proposals::on_finalise(2);
+
+            assert_eq!(Proposals::proposals(1).status, PendingExecution);
+            assert_eq!(Proposals::tally_results(1).approvals, ALL_COUNCILORS.len() as u32 - 1);
+            assert_eq!(Proposals::tally_results(1).slashes, 0);
+        });
+    }
+
     #[test]
     fn approve_proposal_when_all_councilors_voted_and_only_quorum_approved() {
         with_externalities(&mut new_test_ext(), || {
@@ -1106,7 +3519,7 @@ mod tests {
 
             // Only a quorum of councilors approved, others rejected:
             let councilors = Proposals::councilors_count();
-            let approvals = Proposals::approval_quorum_seats();
+            let approvals = Proposals::approval_quorum_seats(ProposalCategory::RuntimeUpgrade);
             let rejections = councilors - approvals;
             for i in 0..councilors as usize {
                 let vote = if (i as u32) < approvals { Approve } else { Reject };
@@ -1120,6 +3533,12 @@ mod tests {
             System::set_block_number(2);
             Proposals::on_finalise(2);
 
+            // Check that runtime code has NOT been updated until the grace period is over.
+            assert_runtime_code_empty!();
+            assert_eq!(Proposals::proposals(1).status, PendingExecution);
+
+            _wait_for_grace_period(2);
+
             // Check that runtime code has been updated after proposal approved.
             assert_runtime_code!(wasm_code());
 
@@ -1131,6 +3550,7 @@ mod tests {
                 approvals: approvals,
                 rejections: rejections,
                 slashes: 0,
+                weighted: None,
                 status: Approved,
                 finalized_at: 2
             });
@@ -1152,7 +3572,7 @@ mod tests {
             assert_ok!(_create_default_proposal());
 
             // Only quorum of councilors approved, other councilors didn't vote:
-            let approvals = Proposals::approval_quorum_seats();
+            let approvals = Proposals::approval_quorum_seats(ProposalCategory::RuntimeUpgrade);
             for i in 0..approvals as usize {
                 let vote = if (i as u32) < approvals { Approve } else { Slash };
                 assert_ok!(Proposals::vote_on_proposal(
@@ -1172,6 +3592,7 @@ mod tests {
 
             System::set_block_number(expiration_block);
             Proposals::on_finalise(expiration_block);
+            _wait_for_grace_period(expiration_block);
 
             // Check that runtime code has been updated after proposal approved.
             assert_runtime_code!(wasm_code());
@@ -1184,6 +3605,7 @@ mod tests {
                 approvals: approvals,
                 rejections: 0,
                 slashes: 0,
+                weighted: None,
                 status: Approved,
                 finalized_at: expiration_block
             });
@@ -1206,7 +3628,7 @@ mod tests {
 
             // Less than a quorum of councilors approved, while others abstained:
             let councilors = Proposals::councilors_count();
-            let approvals = Proposals::approval_quorum_seats() - 1;
+            let approvals = Proposals::approval_quorum_seats(ProposalCategory::RuntimeUpgrade) - 1;
             let abstentions = councilors - approvals;
             for i in 0..councilors as usize {
                 let vote = if (i as u32) < approvals { Approve } else { Abstain };
@@ -1231,6 +3653,7 @@ mod tests {
                 approvals: approvals,
                 rejections: 0,
                 slashes: 0,
+                weighted: None,
                 status: Rejected,
                 finalized_at: 2
             });
@@ -1276,6 +3699,7 @@ mod tests {
                 approvals: 0,
                 rejections: ALL_COUNCILORS.len() as u32,
                 slashes: 0,
+                weighted: None,
                 status: Rejected,
                 finalized_at: 2
             });
@@ -1321,12 +3745,13 @@ mod tests {
                 approvals: 0,
                 rejections: 0,
                 slashes: ALL_COUNCILORS.len() as u32,
+                weighted: None,
                 status: Slashed,
                 finalized_at: 2
             });
 
-            // Check that proposer's balance reduced by burnt stake:
-            assert_eq!(Balances::free_balance(PROPOSER1), initial_balance() - min_stake());
+            // Check that proposer's balance reduced by burnt stake and deposit:
+            assert_eq!(Balances::free_balance(PROPOSER1), initial_balance() - min_stake() - default_deposit());
             assert_eq!(Balances::reserved_balance(PROPOSER1), 0);
 
             // TODO expect event ProposalStatusUpdated(1, Slashed)
@@ -1342,6 +3767,38 @@ mod tests {
 
     // In this case a proposal will be marked as 'Expired'
     // and it will be processed in the same way as if it has been rejected.
+    #[test]
+    fn failed_finalization_of_one_proposal_does_not_block_others() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::set_free_balance(&PROPOSER2, initial_balance());
+            Balances::increase_total_stake_by(initial_balance() * 2);
+
+            assert_ok!(_create_proposal_of_kind(None, ProposalKind::Text));
+            assert_ok!(_create_proposal_of_kind(Some(PROPOSER2), ProposalKind::Text));
+
+            // Break the first proposal, so its status cannot be updated:
+            <ActiveProposalIds<Test>>::put(vec![2]);
+
+            let expiration_block = System::block_number() + Proposals::voting_period();
+            System::set_block_number(expiration_block);
+            Proposals::on_finalise(expiration_block);
+
+            assert_eq!(Proposals::proposals(1).status, Active);
+            assert_eq!(Proposals::proposals(2).status, Expired);
+            assert!(Proposals::active_proposal_ids().is_empty());
+
+            // The failed proposal is queued for the next tally:
+            assert_eq!(Proposals::proposal_ids_to_tally(), vec![1]);
+
+            <ActiveProposalIds<Test>>::put(vec![1]);
+            System::set_block_number(expiration_block + 1);
+            Proposals::on_finalise(expiration_block + 1);
+            assert_eq!(Proposals::proposals(1).status, Expired);
+            assert!(Proposals::proposal_ids_to_tally().is_empty());
+        });
+    }
+
     #[test]
     fn expire_proposal_when_not_all_councilors_voted_and_quorum_not_reached() {
         with_externalities(&mut new_test_ext(), || {
@@ -1351,7 +3808,7 @@ mod tests {
             assert_ok!(_create_default_proposal());
 
             // Less than a quorum of councilors approved:
-            let approvals = Proposals::approval_quorum_seats() - 1;
+            let approvals = Proposals::approval_quorum_seats(ProposalCategory::RuntimeUpgrade) - 1;
             for i in 0..approvals as usize {
                 let vote = if (i as u32) < approvals { Approve } else { Slash };
                 assert_ok!(Proposals::vote_on_proposal(
@@ -1376,6 +3833,7 @@ mod tests {
                 approvals: approvals,
                 rejections: 0,
                 slashes: 0,
+                weighted: None,
                 status: Expired,
                 finalized_at: expiration_block
             });
@@ -1387,4 +3845,262 @@ mod tests {
             // TODO expect event ProposalStatusUpdated(1, Rejected)
         });
     }
+
+    // -------------------------------------------------------------------
+    // Grace period
+
+    #[test]
+    fn approved_proposal_is_executed_after_grace_period() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            assert_ok!(Proposals::set_grace_period(Origin::ROOT, 10));
+            assert_ok!(_create_default_proposal());
+            _all_councilors_vote_and_finalize(1, Approve);
+
+            assert_eq!(Proposals::proposals(1).status, PendingExecution);
+            assert_eq!(Proposals::executes_at(1), 12);
+            assert_eq!(Proposals::scheduled_executions(12), vec![1]);
+
+            // Proposer gets his stake back right after approval:
+            assert_eq!(Balances::free_balance(PROPOSER1), initial_balance());
+
+            System::set_block_number(11);
+            Proposals::on_finalise(11);
+            assert_runtime_code_empty!();
+
+            System::set_block_number(12);
+            Proposals::on_finalise(12);
+            assert_runtime_code!(wasm_code());
+            assert_eq!(Proposals::proposals(1).status, Approved);
+            assert!(Proposals::scheduled_executions(12).is_empty());
+        });
+    }
+
+    #[test]
+    fn root_can_veto_proposal_during_grace_period() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            assert_ok!(_create_default_proposal());
+            _all_councilors_vote_and_finalize(1, Approve);
+            assert_eq!(Proposals::proposals(1).status, PendingExecution);
+
+            assert_ok!(Proposals::veto_proposal(Origin::ROOT, 1));
+            assert_eq!(Proposals::proposals(1).status, Cancelled);
+
+            _wait_for_grace_period(2);
+
+            // Vetoed proposal is not executed:
+            assert_runtime_code_empty!();
+            assert_eq!(Proposals::proposals(1).status, Cancelled);
+            assert_eq!(Balances::free_balance(PROPOSER1), initial_balance());
+
+            assert_eq!(Proposals::veto_proposal(Origin::ROOT, 1), Err(MSG_PROPOSAL_FINALIZED));
+        });
+    }
+
+    #[test]
+    fn council_majority_can_veto_proposal() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            assert_ok!(_create_default_proposal());
+
+            assert!(Proposals::veto_proposal(Origin::signed(COUNCILOR1), 1).is_err());
+            assert!(Proposals::veto_proposal(Origin::from(council::RawOrigin::Members(2, 5)), 1).is_err());
+
+            assert_ok!(Proposals::veto_proposal(Origin::from(council::RawOrigin::Members(3, 5)), 1));
+            assert_eq!(Proposals::proposals(1).status, Cancelled);
+        });
+    }
+
+    #[test]
+    fn failed_execution_is_recorded_in_proposal_status() {
+        with_externalities(&mut new_test_ext(), || {
+            const TREASURY: u64 = 100;
+
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            <TreasuryAccount<Test>>::put(TREASURY);
+
+            // Treasury has no funds to pay for this spend:
+            assert_ok!(_create_proposal_of_kind(None, ProposalKind::TreasurySpend(PROPOSER2, 300)));
+            _all_councilors_vote_and_finalize(1, Approve);
+            _wait_for_grace_period(2);
+
+            assert_eq!(Proposals::proposals(1).status, ExecutionFailed);
+            assert_eq!(Balances::free_balance(PROPOSER2), 0);
+        });
+    }
+
+    // -------------------------------------------------------------------
+    // Slash handlers
+
+    #[test]
+    fn slash_to_treasury_moves_slashed_balance_to_treasury() {
+        with_externalities(&mut new_test_ext(), || {
+            const TREASURY: u64 = 100;
+
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::set_free_balance(&TREASURY, 1);
+            Balances::increase_total_stake_by(initial_balance() + 1);
+            <TreasuryAccount<Test>>::put(TREASURY);
+
+            assert_ok!(_create_default_proposal());
+            let recipients = <SlashToTreasury as OnProposalSlash<Test>>::on_proposal_slash(1, &PROPOSER1, 30);
+
+            assert_eq!(recipients, vec![(TREASURY, 30)]);
+            assert_eq!(Balances::free_balance(TREASURY), 31);
+            assert_eq!(Balances::reserved_balance(PROPOSER1), min_stake() + default_deposit() - 30);
+        });
+    }
+
+    #[test]
+    fn slash_to_treasury_burns_balance_if_treasury_does_not_exist() {
+        with_externalities(&mut new_test_ext(), || {
+            const TREASURY: u64 = 100;
+
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+            <TreasuryAccount<Test>>::put(TREASURY);
+
+            assert_ok!(_create_default_proposal());
+            let recipients = <SlashToTreasury as OnProposalSlash<Test>>::on_proposal_slash(1, &PROPOSER1, 30);
+
+            assert!(recipients.is_empty());
+            assert_eq!(Balances::free_balance(TREASURY), 0);
+            assert_eq!(Balances::reserved_balance(PROPOSER1), min_stake() + default_deposit() - 30);
+        });
+    }
+
+    #[test]
+    fn slash_to_voters_splits_slashed_balance_among_voters() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::set_free_balance(&COUNCILOR1, 1);
+            Balances::set_free_balance(&COUNCILOR2, 1);
+            Balances::increase_total_stake_by(initial_balance() + 2);
+
+            assert_ok!(_create_default_proposal());
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR1), 1, Slash));
+            assert_ok!(Proposals::vote_on_proposal(Origin::signed(COUNCILOR2), 1, Reject));
+
+            let recipients = <SlashToVoters as OnProposalSlash<Test>>::on_proposal_slash(1, &PROPOSER1, 31);
+
+            // One unit that cannot be split equally is burned:
+            assert_eq!(recipients, vec![(COUNCILOR1, 15), (COUNCILOR2, 15)]);
+            assert_eq!(Balances::free_balance(COUNCILOR1), 16);
+            assert_eq!(Balances::free_balance(COUNCILOR2), 16);
+            assert_eq!(Balances::reserved_balance(PROPOSER1), min_stake() + default_deposit() - 31);
+        });
+    }
+
+    // -------------------------------------------------------------------
+    // History and pruning
+
+    #[test]
+    fn proposals_are_indexed_by_proposer_and_status() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 2);
+            Balances::set_free_balance(&PROPOSER2, initial_balance());
+            Balances::increase_total_stake_by(initial_balance() * 3);
+
+            assert_ok!(_create_default_proposal());
+            assert_ok!(_create_proposal(Some(PROPOSER2), None, None, None, None));
+            assert_ok!(_create_proposal_of_kind(None, ProposalKind::Text));
+
+            assert_eq!(Proposals::proposal_ids_by_proposer(PROPOSER1), vec![1, 3]);
+            assert_eq!(Proposals::proposal_ids_by_proposer(PROPOSER2), vec![2]);
+            assert_eq!(Proposals::proposal_ids_by_status(Active), vec![1, 2, 3]);
+
+            assert_ok!(Proposals::cancel_proposal(Origin::signed(PROPOSER1), 1));
+            _all_councilors_vote_and_finalize(3, Approve);
+
+            assert_eq!(Proposals::proposal_ids_by_status(Active), vec![2]);
+            assert_eq!(Proposals::proposal_count_by_status(Active), 1);
+            assert_eq!(Proposals::proposal_ids_by_status(Cancelled), vec![1]);
+            assert_eq!(Proposals::proposal_ids_by_status(PendingExecution), vec![3]);
+
+            _wait_for_grace_period(2);
+            assert!(Proposals::proposal_ids_by_status(PendingExecution).is_empty());
+            assert_eq!(Proposals::proposal_ids_by_status(Approved), vec![3]);
+        });
+    }
+
+    #[test]
+    fn finalized_proposal_is_pruned_after_retention_period() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance());
+            Balances::increase_total_stake_by(initial_balance());
+
+            assert_ok!(Proposals::set_retention_period(Origin::ROOT, 5));
+            assert_ok!(_create_default_proposal());
+            _all_councilors_vote_and_finalize(1, Reject);
+
+            let summary = ProposalSummary {
+                id: 1,
+                proposer: PROPOSER1,
+                status: Rejected,
+                votes: VoteCounts {
+                    abstentions: 0,
+                    approvals: 0,
+                    rejections: ALL_COUNCILORS.len() as u32,
+                    slashes: 0,
+                },
+                finalized_at: 2,
+            };
+            assert_eq!(Proposals::proposal_summaries(1), summary);
+
+            // Details and votes are kept until the retention period is over:
+            System::set_block_number(6);
+            Proposals::on_finalise(6);
+            assert!(<Proposals<Test>>::exists(1));
+
+            System::set_block_number(7);
+            Proposals::on_finalise(7);
+
+            assert!(!<Proposals<Test>>::exists(1));
+            assert!(!<TallyResults<Test>>::exists(1));
+            assert!(!<VotesByProposal<Test>>::exists(1));
+            assert!(!<VoteByAccountAndProposal<Test>>::exists((COUNCILOR1, 1)));
+            assert!(!<VoteCountsByProposal<Test>>::exists(1));
+
+            // Only the summary is kept, pruned proposal is removed from indexes:
+            assert_eq!(Proposals::proposal_summaries(1), summary);
+            assert!(Proposals::proposal_ids_by_status(Rejected).is_empty());
+            assert!(Proposals::proposal_ids_by_proposer(PROPOSER1).is_empty());
+            assert!(!<StatusPositionOfProposal<Test>>::exists(1));
+            assert!(!<ProposerPositionOfProposal<Test>>::exists(1));
+        });
+    }
+
+    #[test]
+    fn pruned_proposal_is_replaced_by_last_proposal_in_indexes() {
+        with_externalities(&mut new_test_ext(), || {
+            Balances::set_free_balance(&PROPOSER1, initial_balance() * 3);
+            Balances::increase_total_stake_by(initial_balance() * 3);
+
+            assert_ok!(Proposals::set_retention_period(Origin::ROOT, 5));
+            assert_ok!(_create_default_proposal());
+            assert_ok!(_create_proposal_of_kind(None, ProposalKind::Text));
+            assert_ok!(_create_proposal_of_kind(None, ProposalKind::Text));
+
+            System::set_block_number(1);
+            assert_ok!(Proposals::cancel_proposal(Origin::signed(PROPOSER1), 1));
+            System::set_block_number(2);
+            assert_ok!(Proposals::cancel_proposal(Origin::signed(PROPOSER1), 3));
+            assert_eq!(Proposals::proposal_ids_by_status(Cancelled), vec![1, 3]);
+
+            System::set_block_number(6);
+            Proposals::on_finalise(6);
+
+            assert!(!<Proposals<Test>>::exists(1));
+            assert_eq!(Proposals::proposal_ids_by_proposer(PROPOSER1), vec![3, 2]);
+            assert_eq!(Proposals::proposal_ids_by_status(Cancelled), vec![3]);
+            assert_eq!(Proposals::proposal_ids_by_status(Active), vec![2]);
+        });
+    }
 }
@@ -0,0 +1,282 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_codec::Decode;
+use rstd::prelude::*;
+
+pub const MSG_WASM_INVALID_HEADER: &str = "WASM code does not start with a valid module header";
+pub const MSG_WASM_MALFORMED_SECTION: &str = "WASM code has a malformed section";
+pub const MSG_WASM_MISSING_EXPORT: &str = "WASM code does not export required runtime API functions";
+pub const MSG_WASM_MISSING_RUNTIME_VERSION: &str = "WASM code does not embed its runtime version";
+pub const MSG_WASM_INVALID_RUNTIME_VERSION: &str = "WASM code has a runtime version that cannot be decoded";
+pub const MSG_WASM_SPEC_NAME_MISMATCH: &str = "Runtime spec name differs from the current runtime";
+pub const MSG_WASM_SPEC_VERSION_NOT_HIGHER: &str = "Runtime spec version must be higher than the current one";
+
+const WASM_MAGIC: [u8; 4] = [0x00, 0x61, 0x73, 0x6d];
+const WASM_VERSION: [u8; 4] = [0x01, 0x00, 0x00, 0x00];
+
+const CUSTOM_SECTION_ID: u8 = 0;
+const EXPORT_SECTION_ID: u8 = 7;
+const FUNCTION_EXPORT_KIND: u8 = 0;
+
+/// Functions of the runtime API that every runtime must export.
+pub const REQUIRED_EXPORTS: [&[u8]; 2] = [b"Core_version", b"Core_execute_block"];
+
+/// Name of a custom section with SCALE encoded `RuntimeVersion` of the code.
+pub const RUNTIME_VERSION_SECTION: &[u8] = b"runtime_version";
+
+/// The part of `RuntimeVersion` that is checked before a runtime upgrade.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SpecVersion {
+    pub spec_name: Vec<u8>,
+    pub spec_version: u32,
+}
+
+/// Check that the code is a well-formed WASM module that exports the runtime API
+/// and embeds its runtime version, which must have the same spec name
+/// and a higher spec version than the current runtime.
+///
+/// The version cannot be read from `Core_version` without executing the code, so it is read
+/// from the `runtime_version` section instead. This runtime embeds the section itself,
+/// see `ENCODED_RUNTIME_VERSION`. Code without the section is rejected.
+pub fn validate(code: &[u8], current: &SpecVersion) -> Result<(), &'static str> {
+    let mut reader = Reader::new(code);
+    if reader.read_bytes(4) != Some(&WASM_MAGIC[..]) || reader.read_bytes(4) != Some(&WASM_VERSION[..]) {
+        return Err(MSG_WASM_INVALID_HEADER);
+    }
+
+    let mut exports: Vec<&[u8]> = vec![];
+    let mut version: Option<SpecVersion> = None;
+
+    while !reader.is_empty() {
+        let id = reader.read_byte().ok_or(MSG_WASM_MALFORMED_SECTION)?;
+        let size = reader.read_var_u32().ok_or(MSG_WASM_MALFORMED_SECTION)?;
+        let payload = reader.read_bytes(size as usize).ok_or(MSG_WASM_MALFORMED_SECTION)?;
+        let mut section = Reader::new(payload);
+
+        match id {
+            CUSTOM_SECTION_ID => {
+                let name = section.read_name().ok_or(MSG_WASM_MALFORMED_SECTION)?;
+                if name == RUNTIME_VERSION_SECTION {
+                    version = Some(decode_spec_version(section.rest())?);
+                }
+            },
+            EXPORT_SECTION_ID => {
+                let count = section.read_var_u32().ok_or(MSG_WASM_MALFORMED_SECTION)?;
+                for _ in 0..count {
+                    let name = section.read_name().ok_or(MSG_WASM_MALFORMED_SECTION)?;
+                    let kind = section.read_byte().ok_or(MSG_WASM_MALFORMED_SECTION)?;
+                    section.read_var_u32().ok_or(MSG_WASM_MALFORMED_SECTION)?;
+                    if kind == FUNCTION_EXPORT_KIND {
+                        exports.push(name);
+                    }
+                }
+            },
+            _ => { /* other sections are not checked */ },
+        }
+    }
+
+    let has_all_exports = REQUIRED_EXPORTS.iter().all(|required| exports.contains(required));
+    if !has_all_exports {
+        return Err(MSG_WASM_MISSING_EXPORT);
+    }
+
+    let version = version.ok_or(MSG_WASM_MISSING_RUNTIME_VERSION)?;
+    if version.spec_name != current.spec_name {
+        return Err(MSG_WASM_SPEC_NAME_MISMATCH);
+    }
+    if version.spec_version <= current.spec_version {
+        return Err(MSG_WASM_SPEC_VERSION_NOT_HIGHER);
+    }
+
+    Ok(())
+}
+
+/// Decode the leading fields of SCALE encoded `RuntimeVersion`:
+/// spec name, impl name, authoring version and spec version.
+fn decode_spec_version(mut input: &[u8]) -> Result<SpecVersion, &'static str> {
+    let spec_name = <Vec<u8>>::decode(&mut input).ok_or(MSG_WASM_INVALID_RUNTIME_VERSION)?;
+    let _impl_name = <Vec<u8>>::decode(&mut input).ok_or(MSG_WASM_INVALID_RUNTIME_VERSION)?;
+    let _authoring_version = u32::decode(&mut input).ok_or(MSG_WASM_INVALID_RUNTIME_VERSION)?;
+    let spec_version = u32::decode(&mut input).ok_or(MSG_WASM_INVALID_RUNTIME_VERSION)?;
+    Ok(SpecVersion { spec_name, spec_version })
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        self.data
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        self.read_bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.data.len() {
+            return None;
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(bytes)
+    }
+
+    /// Read unsigned LEB128 encoded integer of at most 32 bits.
+    fn read_var_u32(&mut self) -> Option<u32> {
+        let mut result: u32 = 0;
+        for i in 0..5 {
+            let byte = self.read_byte()?;
+            let value = (byte & 0x7f) as u32;
+            if i == 4 && value > 0x0f {
+                return None;
+            }
+            result |= value << (i * 7);
+            if byte & 0x80 == 0 {
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    fn read_name(&mut self) -> Option<&'a [u8]> {
+        let len = self.read_var_u32()?;
+        self.read_bytes(len as usize)
+    }
+}
+
+/// Helpers for tests of modules that validate WASM code.
+#[cfg(test)]
+pub mod test_module {
+    use super::*;
+
+    /// Build a minimal WASM module with given function exports
+    /// and optionally a runtime version section.
+    pub fn build(exports: &[&[u8]], version: Option<(&[u8], u32)>) -> Vec<u8> {
+        use parity_codec::Encode;
+
+        fn push_var_u32(out: &mut Vec<u8>, mut value: u32) {
+            loop {
+                let byte = (value & 0x7f) as u8;
+                value >>= 7;
+                if value == 0 {
+                    out.push(byte);
+                    return;
+                }
+                out.push(byte | 0x80);
+            }
+        }
+
+        fn push_section(out: &mut Vec<u8>, id: u8, payload: Vec<u8>) {
+            out.push(id);
+            push_var_u32(out, payload.len() as u32);
+            out.extend(payload);
+        }
+
+        let mut module = vec![];
+        module.extend_from_slice(&WASM_MAGIC);
+        module.extend_from_slice(&WASM_VERSION);
+
+        let mut export_section = vec![];
+        push_var_u32(&mut export_section, exports.len() as u32);
+        for (index, name) in exports.iter().enumerate() {
+            push_var_u32(&mut export_section, name.len() as u32);
+            export_section.extend_from_slice(name);
+            export_section.push(FUNCTION_EXPORT_KIND);
+            push_var_u32(&mut export_section, index as u32);
+        }
+        push_section(&mut module, EXPORT_SECTION_ID, export_section);
+
+        if let Some((spec_name, spec_version)) = version {
+            let mut version_section = vec![];
+            push_var_u32(&mut version_section, RUNTIME_VERSION_SECTION.len() as u32);
+            version_section.extend_from_slice(RUNTIME_VERSION_SECTION);
+            version_section.extend(spec_name.to_vec().encode());
+            version_section.extend(spec_name.to_vec().encode());
+            version_section.extend(1u32.encode());
+            version_section.extend(spec_version.encode());
+            push_section(&mut module, CUSTOM_SECTION_ID, version_section);
+        }
+
+        module
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn current() -> SpecVersion {
+        SpecVersion {
+            spec_name: b"joystream-node".to_vec(),
+            spec_version: 4,
+        }
+    }
+
+    #[test]
+    fn valid_module_passes() {
+        assert_eq!(validate(&test_module::build(&REQUIRED_EXPORTS, Some((&b"joystream-node"[..], 5))), &current()), Ok(()));
+    }
+
+    #[test]
+    fn module_without_runtime_version_is_rejected() {
+        assert_eq!(validate(&test_module::build(&REQUIRED_EXPORTS, None), &current()),
+            Err(MSG_WASM_MISSING_RUNTIME_VERSION));
+    }
+
+    #[test]
+    fn embedded_runtime_version_matches_current_version() {
+        use parity_codec::Encode;
+
+        let version = crate::VERSION;
+        let mut expected = version.spec_name.as_bytes().to_vec().encode();
+        expected.extend(version.impl_name.as_bytes().to_vec().encode());
+        expected.extend(version.authoring_version.encode());
+        expected.extend(version.spec_version.encode());
+        assert_eq!(crate::ENCODED_RUNTIME_VERSION.to_vec(), expected);
+    }
+
+    #[test]
+    fn invalid_header_is_rejected() {
+        assert_eq!(validate(b"Proposal Wasm Code", &current()), Err(MSG_WASM_INVALID_HEADER));
+        assert_eq!(validate(&WASM_MAGIC[..], &current()), Err(MSG_WASM_INVALID_HEADER));
+    }
+
+    #[test]
+    fn truncated_section_is_rejected() {
+        let mut module = test_module::build(&REQUIRED_EXPORTS, None);
+        module.pop();
+        assert_eq!(validate(&module, &current()), Err(MSG_WASM_MALFORMED_SECTION));
+    }
+
+    #[test]
+    fn missing_export_is_rejected() {
+        let module = test_module::build(&[&b"Core_version"[..]], None);
+        assert_eq!(validate(&module, &current()), Err(MSG_WASM_MISSING_EXPORT));
+    }
+
+    #[test]
+    fn other_runtime_is_rejected() {
+        let module = test_module::build(&REQUIRED_EXPORTS, Some((&b"other-node"[..], 5)));
+        assert_eq!(validate(&module, &current()), Err(MSG_WASM_SPEC_NAME_MISMATCH));
+    }
+
+    #[test]
+    fn downgrade_is_rejected() {
+        let same = test_module::build(&REQUIRED_EXPORTS, Some((&b"joystream-node"[..], 4)));
+        assert_eq!(validate(&same, &current()), Err(MSG_WASM_SPEC_VERSION_NOT_HIGHER));
+
+        let lower = test_module::build(&REQUIRED_EXPORTS, Some((&b"joystream-node"[..], 3)));
+        assert_eq!(validate(&lower, &current()), Err(MSG_WASM_SPEC_VERSION_NOT_HIGHER));
+    }
+}
@@ -52,6 +52,26 @@ impl<AccountId, Stake, Hash, Vote> SealedVote<AccountId, Stake, Hash, Vote>
         }
     }
 
+    /// Unseals a ranked ballot. The commitment is a salted hash of the serialized ranking,
+    /// and the first choice of the ranking becomes the vote.
+    pub fn unseal_ranked(&mut self, ranking: &[Vote], salt: &mut Vec<u8>, hasher: fn(&[u8]) -> Hash) -> Result<(), &'static str>
+        where Vote: Clone
+    {
+        // only unseal once
+        ensure!(self.is_not_revealed(), "vote already unsealed");
+        ensure!(!ranking.is_empty(), "ranking is empty");
+
+        let mut payload = ranking.encode();
+        payload.append(salt);
+
+        if self.commitment == hasher(&payload) {
+            self.vote = Some(ranking[0].clone());
+            Ok(())
+        } else {
+            Err("invalid salt")
+        }
+    }
+
     pub fn get_vote(&self) -> &Option<Vote> {
         &self.vote
     }
@@ -15,8 +15,11 @@ use substrate_client as client;
 extern crate parity_codec_derive;
 
 pub mod governance;
-use governance::{election, council, proposals};
+use governance::{election, council, proposals, motions};
 mod memo;
+pub mod membership;
+use membership::members;
+pub mod traits;
 
 use rstd::prelude::*;
 #[cfg(feature = "std")]
@@ -92,6 +95,21 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	apis: RUNTIME_API_VERSIONS,
 };
 
+/// Leading fields of SCALE encoded `VERSION`: spec name, impl name, authoring version and spec version.
+/// The WASM runtime embeds them in a custom section, so that a runtime upgrade proposal can check
+/// the version of proposed code. A test checks that they match `VERSION`.
+pub const ENCODED_RUNTIME_VERSION: [u8; 38] = [
+	56, b'j', b'o', b'y', b's', b't', b'r', b'e', b'a', b'm', b'-', b'n', b'o', b'd', b'e',
+	56, b'j', b'o', b'y', b's', b't', b'r', b'e', b'a', b'm', b'-', b'n', b'o', b'd', b'e',
+	3, 0, 0, 0,
+	4, 0, 0, 0,
+];
+
+#[cfg(not(feature = "std"))]
+#[link_section = "runtime_version"]
+#[used]
+static RUNTIME_VERSION_SECTION: [u8; 38] = ENCODED_RUNTIME_VERSION;
+
 /// The version infromation used to identify this runtime when compiled natively.
 #[cfg(feature = "std")]
 pub fn native_version() -> NativeVersion {
@@ -208,20 +226,39 @@ impl governance::GovernanceCurrency for Runtime {
 
 impl governance::proposals::Trait for Runtime {
 	type Event = Event;
+	/// Burn stakes and fees slashed from proposers.
+	type OnProposalSlash = ();
+	type Members = Members;
 }
 
 impl governance::election::Trait for Runtime {
 	type Event = Event;
 	type CouncilElected = (Council,);
+	type Members = Members;
 }
 
 impl governance::council::Trait for Runtime {
 	type Event = Event;
 	type CouncilTermEnded = (CouncilElection,);
+	/// Privileged governance calls can be made by sudo or by a majority of the council.
+	type CouncilOrigin = council::RootOrCouncilMajority<Runtime>;
+}
+
+impl governance::motions::Trait for Runtime {
+	type Origin = Origin;
+	/// Councilors can propose any runtime call as a motion.
+	type Proposal = Call;
+	type Event = Event;
 }
 
 impl memo::Trait for Runtime {
 	type Event = Event;
+	type Members = Members;
+}
+
+impl members::Trait for Runtime {
+	type Event = Event;
+	type CouncilOrigin = council::RootOrCouncilMajority<Runtime>;
 }
 
 construct_runtime!(
@@ -242,8 +279,10 @@ construct_runtime!(
 		Sudo: sudo,
 		Proposals: proposals::{Module, Call, Storage, Event<T>, Config<T>},
 		CouncilElection: election::{Module, Call, Storage, Event<T>, Config<T>},
-		Council: council::{Module, Call, Storage, Event<T>, Config<T>},
+		Council: council::{Module, Call, Storage, Event<T>, Config<T>, Origin},
+		CouncilMotions: motions::{Module, Call, Storage, Event<T>},
 		Memo: memo::{Module, Call, Storage, Event<T>},
+		Members: members::{Module, Call, Storage, Event<T>, Config<T>},
 	}
 );
 
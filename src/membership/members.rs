@@ -0,0 +1,739 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use rstd::prelude::*;
+use srml_support::{StorageValue, StorageMap, dispatch::Result, decl_module, decl_event, decl_storage, ensure};
+use srml_support::traits::{Currency};
+use system::{self, ensure_signed};
+use runtime_primitives::traits::{As, Zero};
+
+use crate::governance::{GovernanceCurrency, BalanceOf};
+use crate::governance::council::EnsureCouncilOrigin;
+use crate::traits::Members;
+
+const DEFAULT_PAID_TERMS_ID: u32 = 1;
+const DEFAULT_PAID_TERMS_FEE: u64 = 100;
+// Approximately one year if a block is produced every 6 seconds.
+const DEFAULT_PAID_TERMS_DURATION: u64 = 5_256_000;
+const DEFAULT_INVITES_PER_MEMBER: u32 = 5;
+const DEFAULT_MIN_HANDLE_LENGTH: u32 = 5;
+const DEFAULT_MAX_HANDLE_LENGTH: u32 = 40;
+const DEFAULT_HANDLE_ALLOWED_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789_-";
+const DEFAULT_MAX_AVATAR_URI_LENGTH: u32 = 1024;
+const DEFAULT_MAX_ABOUT_TEXT_LENGTH: u32 = 2048;
+
+const MSG_ALREADY_A_MEMBER: &str = "Account is already a member";
+const MSG_NOT_A_MEMBER: &str = "Account is not a member";
+const MSG_MEMBERSHIP_EXPIRED: &str = "Membership of account has expired";
+const MSG_NEW_MEMBERS_NOT_ALLOWED: &str = "New members are not allowed at the moment";
+const MSG_PAID_TERMS_NOT_FOUND: &str = "There are no active paid terms with such id";
+const MSG_PAID_TERMS_ALREADY_EXIST: &str = "Paid terms with such id already exist";
+const MSG_ZERO_PAID_TERMS_DURATION: &str = "Duration of paid terms should be greater than zero";
+const MSG_NOT_ENOUGH_BALANCE_TO_BUY_MEMBERSHIP: &str = "Not enough balance to pay the membership fee";
+const MSG_NO_INVITES_LEFT: &str = "Member has no invites left";
+const MSG_TOO_SHORT_HANDLE: &str = "Handle is too short";
+const MSG_TOO_LONG_HANDLE: &str = "Handle is too long";
+const MSG_HANDLE_HAS_INVALID_CHARS: &str = "Handle contains characters that are not allowed";
+const MSG_HANDLE_ALREADY_TAKEN: &str = "Handle is already taken by another member";
+const MSG_TOO_LONG_AVATAR_URI: &str = "Avatar URI is too long";
+const MSG_TOO_LONG_ABOUT_TEXT: &str = "About text is too long";
+const MSG_INVALID_HANDLE_LENGTH_LIMITS: &str = "Min handle length should be greater than zero and not greater than max length";
+
+pub type MemberId = u32;
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+/// How a member has joined.
+pub enum EntryMethod<AccountId> {
+    /// Paid a fee of paid terms with a given id.
+    Paid(u32),
+    /// Was invited by a member with a given account.
+    Invited(AccountId),
+    /// Was added in the genesis block.
+    Genesis,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+pub struct Profile<AccountId, BlockNumber> {
+    pub id: MemberId,
+    pub account: AccountId,
+    pub handle: Vec<u8>,
+    pub avatar_uri: Vec<u8>,
+    pub about: Vec<u8>,
+    pub registered_at: BlockNumber,
+    /// A block at which membership expires unless it is renewed.
+    pub expires_at: BlockNumber,
+    pub entry: EntryMethod<AccountId>,
+    /// How many more accounts this member can invite.
+    pub invites: u32,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+/// A fee that should be paid for a membership that lasts a given number of blocks.
+pub struct PaidMembershipTerms<Balance, BlockNumber> {
+    pub id: u32,
+    pub fee: Balance,
+    pub duration: BlockNumber,
+}
+
+/// Data provided by an account when it registers as a member.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+pub struct UserInfo {
+    pub handle: Vec<u8>,
+    pub avatar_uri: Vec<u8>,
+    pub about: Vec<u8>,
+}
+
+/// Profile fields to be updated by a member. `None` leaves a field unchanged.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+pub struct UserInfoUpdate {
+    pub handle: Option<Vec<u8>>,
+    pub avatar_uri: Option<Vec<u8>>,
+    pub about: Option<Vec<u8>>,
+}
+
+pub trait Trait: system::Trait + GovernanceCurrency {
+    type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+    /// Origin that is allowed to change membership parameters.
+    type CouncilOrigin: EnsureCouncilOrigin<Self::Origin>;
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as Membership {
+
+        /// Count of all members that have ever registered. Also the id of the last member.
+        MemberCount get(member_count) build(|config: &GenesisConfig<T>| {
+            config.members.len() as MemberId
+        }): MemberId;
+
+        MemberIdByAccountId get(member_id_by_account_id) build(|config: &GenesisConfig<T>| {
+            config.members.iter().enumerate()
+                .map(|(i, (account, _, _))| (account.clone(), i as MemberId + 1))
+                .collect::<Vec<_>>()
+        }): map T::AccountId => Option<MemberId>;
+
+        MemberProfile get(member_profile) build(|config: &GenesisConfig<T>| {
+            config.members.iter().enumerate()
+                .map(|(i, (account, user_info, expires_at))| {
+                    let id = i as MemberId + 1;
+                    (id, Profile {
+                        id,
+                        account: account.clone(),
+                        handle: user_info.handle.clone(),
+                        avatar_uri: user_info.avatar_uri.clone(),
+                        about: user_info.about.clone(),
+                        registered_at: T::BlockNumber::zero(),
+                        expires_at: *expires_at,
+                        entry: EntryMethod::Genesis,
+                        invites: config.invites_per_member,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }): map MemberId => Option<Profile<T::AccountId, T::BlockNumber>>;
+
+        /// Reverse index to find a member by a handle.
+        /// A handle of an expired member can be taken by another account.
+        AccountIdByHandle get(account_id_by_handle) build(|config: &GenesisConfig<T>| {
+            config.members.iter()
+                .map(|(account, user_info, _)| (user_info.handle.clone(), account.clone()))
+                .collect::<Vec<_>>()
+        }): map Vec<u8> => Option<T::AccountId>;
+
+        // Parameters:
+
+        /// Whether new accounts can buy membership or be invited.
+        NewMembershipsAllowed get(new_memberships_allowed) config(): bool = true;
+
+        /// Terms on which membership can be bought or renewed. A paid fee is burned.
+        ActivePaidTerms get(active_paid_terms) config(): Vec<PaidMembershipTerms<BalanceOf<T>, T::BlockNumber>> =
+            vec![PaidMembershipTerms {
+                id: DEFAULT_PAID_TERMS_ID,
+                fee: BalanceOf::<T>::sa(DEFAULT_PAID_TERMS_FEE),
+                duration: T::BlockNumber::sa(DEFAULT_PAID_TERMS_DURATION),
+            }];
+
+        /// Number of invites granted to a member who has paid for membership.
+        InvitesPerMember get(invites_per_member) config(): u32 = DEFAULT_INVITES_PER_MEMBER;
+
+        MinHandleLength get(min_handle_length) config(): u32 = DEFAULT_MIN_HANDLE_LENGTH;
+        MaxHandleLength get(max_handle_length) config(): u32 = DEFAULT_MAX_HANDLE_LENGTH;
+        HandleAllowedChars get(handle_allowed_chars) config(): Vec<u8> = DEFAULT_HANDLE_ALLOWED_CHARS.to_vec();
+        MaxAvatarUriLength get(max_avatar_uri_length) config(): u32 = DEFAULT_MAX_AVATAR_URI_LENGTH;
+        MaxAboutTextLength get(max_about_text_length) config(): u32 = DEFAULT_MAX_ABOUT_TEXT_LENGTH;
+    }
+    add_extra_genesis {
+        /// Members of the genesis block: account, profile and a block at which membership expires.
+        /// Each of them gets the genesis number of invites.
+        config(members): Vec<(T::AccountId, UserInfo, T::BlockNumber)>;
+    }
+}
+
+decl_event! {
+    pub enum Event<T> where <T as system::Trait>::AccountId, <T as system::Trait>::BlockNumber {
+        /// Params:
+        /// * Id of a new member.
+        /// * Account of a new member.
+        MemberRegistered(MemberId, AccountId),
+
+        /// Params:
+        /// * Id of a new member.
+        /// * Account of a member who has sent the invite.
+        MemberInvited(MemberId, AccountId),
+
+        MemberUpdatedProfile(MemberId),
+
+        /// Params:
+        /// * Id of a member.
+        /// * A block at which the renewed membership expires.
+        MembershipRenewed(MemberId, BlockNumber),
+
+        PaidTermsAdded(u32),
+        PaidTermsRemoved(u32),
+    }
+}
+
+impl<T: Trait> Members<T> for Module<T> {
+    fn is_active_member(account: &T::AccountId) -> bool {
+        Self::member_id_by_account_id(account)
+            .and_then(|id| Self::member_profile(id))
+            .map_or(false, |profile| !Self::is_expired(&profile))
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        fn deposit_event<T>() = default;
+
+        /// Become a member by paying a fee of given paid terms.
+        fn buy_membership(origin, paid_terms_id: u32, user_info: UserInfo) {
+            let who = ensure_signed(origin)?;
+            Self::ensure_can_register(&who, &user_info)?;
+
+            let terms = Self::ensure_active_paid_terms(paid_terms_id)?;
+            Self::pay_fee(&who, terms.fee)?;
+
+            let expires_at = <system::Module<T>>::block_number() + terms.duration;
+            let entry = EntryMethod::Paid(paid_terms_id);
+            let id = Self::insert_member(who.clone(), user_info, entry, expires_at, Self::invites_per_member());
+            Self::deposit_event(RawEvent::MemberRegistered(id, who));
+        }
+
+        /// Register a new member for free by spending one of the sender's invites.
+        /// Membership of an invited member expires together with the inviter's membership.
+        fn invite_member(origin, new_member: T::AccountId, user_info: UserInfo) {
+            let inviter = ensure_signed(origin)?;
+            let (inviter_id, mut inviter_profile) = Self::ensure_active_member(&inviter)?;
+            ensure!(inviter_profile.invites > 0, MSG_NO_INVITES_LEFT);
+
+            Self::ensure_can_register(&new_member, &user_info)?;
+
+            inviter_profile.invites -= 1;
+            let expires_at = inviter_profile.expires_at;
+            <MemberProfile<T>>::insert(inviter_id, inviter_profile);
+
+            let entry = EntryMethod::Invited(inviter.clone());
+            let id = Self::insert_member(new_member.clone(), user_info, entry, expires_at, 0);
+            Self::deposit_event(RawEvent::MemberRegistered(id, new_member));
+            Self::deposit_event(RawEvent::MemberInvited(id, inviter));
+        }
+
+        /// Extend membership by a duration of given paid terms.
+        /// An expired membership is extended from the current block.
+        fn renew_membership(origin, paid_terms_id: u32) {
+            let who = ensure_signed(origin)?;
+            let id = Self::member_id_by_account_id(&who).ok_or(MSG_NOT_A_MEMBER)?;
+            let mut profile = Self::member_profile(id).ok_or(MSG_NOT_A_MEMBER)?;
+
+            let terms = Self::ensure_active_paid_terms(paid_terms_id)?;
+            Self::pay_fee(&who, terms.fee)?;
+
+            let now = <system::Module<T>>::block_number();
+            let renew_from = if Self::is_expired(&profile) { now } else { profile.expires_at };
+            profile.expires_at = renew_from + terms.duration;
+
+            let expires_at = profile.expires_at;
+            <MemberProfile<T>>::insert(id, profile);
+            Self::deposit_event(RawEvent::MembershipRenewed(id, expires_at));
+        }
+
+        fn update_profile(origin, user_info: UserInfoUpdate) {
+            let who = ensure_signed(origin)?;
+            let (id, mut profile) = Self::ensure_active_member(&who)?;
+
+            if let Some(ref avatar_uri) = user_info.avatar_uri {
+                Self::validate_avatar_uri(avatar_uri)?;
+            }
+            if let Some(ref about) = user_info.about {
+                Self::validate_about_text(about)?;
+            }
+            if let Some(ref handle) = user_info.handle {
+                if *handle != profile.handle {
+                    Self::validate_handle(handle)?;
+                }
+            }
+
+            if let Some(handle) = user_info.handle {
+                if handle != profile.handle {
+                    <AccountIdByHandle<T>>::remove(&profile.handle);
+                    Self::assign_handle(&handle, &who);
+                    profile.handle = handle;
+                }
+            }
+            if let Some(avatar_uri) = user_info.avatar_uri {
+                profile.avatar_uri = avatar_uri;
+            }
+            if let Some(about) = user_info.about {
+                profile.about = about;
+            }
+
+            <MemberProfile<T>>::insert(id, profile);
+            Self::deposit_event(RawEvent::MemberUpdatedProfile(id));
+        }
+
+        // Privileged methods
+
+        fn set_new_memberships_allowed(origin, allowed: bool) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            <NewMembershipsAllowed<T>>::put(allowed);
+        }
+
+        fn add_paid_terms(origin, terms: PaidMembershipTerms<BalanceOf<T>, T::BlockNumber>) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(!terms.duration.is_zero(), MSG_ZERO_PAID_TERMS_DURATION);
+            let id = terms.id;
+            ensure!(Self::ensure_active_paid_terms(id).is_err(), MSG_PAID_TERMS_ALREADY_EXIST);
+
+            <ActivePaidTerms<T>>::mutate(|active_terms| active_terms.push(terms));
+            Self::deposit_event(RawEvent::PaidTermsAdded(id));
+        }
+
+        /// Members who have bought membership on removed terms keep it until it expires.
+        fn remove_paid_terms(origin, paid_terms_id: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            Self::ensure_active_paid_terms(paid_terms_id)?;
+
+            <ActivePaidTerms<T>>::mutate(|active_terms| active_terms.retain(|terms| terms.id != paid_terms_id));
+            Self::deposit_event(RawEvent::PaidTermsRemoved(paid_terms_id));
+        }
+
+        fn set_invites_per_member(origin, new_value: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            <InvitesPerMember<T>>::put(new_value);
+        }
+
+        /// Add invites to an existing member.
+        fn add_invites(origin, account: T::AccountId, count: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            let id = Self::member_id_by_account_id(&account).ok_or(MSG_NOT_A_MEMBER)?;
+            let mut profile = Self::member_profile(id).ok_or(MSG_NOT_A_MEMBER)?;
+            profile.invites = profile.invites.saturating_add(count);
+            <MemberProfile<T>>::insert(id, profile);
+        }
+
+        /// Set rules for handles of new members and of profile updates.
+        /// Handles that have been registered already are not affected.
+        fn set_handle_rules(origin, min_length: u32, max_length: u32, allowed_chars: Vec<u8>) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(min_length > 0 && min_length <= max_length, MSG_INVALID_HANDLE_LENGTH_LIMITS);
+            <MinHandleLength<T>>::put(min_length);
+            <MaxHandleLength<T>>::put(max_length);
+            <HandleAllowedChars<T>>::put(allowed_chars);
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+
+    fn is_expired(profile: &Profile<T::AccountId, T::BlockNumber>) -> bool {
+        <system::Module<T>>::block_number() >= profile.expires_at
+    }
+
+    fn ensure_active_member(
+        account: &T::AccountId
+    ) -> rstd::result::Result<(MemberId, Profile<T::AccountId, T::BlockNumber>), &'static str> {
+        let id = Self::member_id_by_account_id(account).ok_or(MSG_NOT_A_MEMBER)?;
+        let profile = Self::member_profile(id).ok_or(MSG_NOT_A_MEMBER)?;
+        ensure!(!Self::is_expired(&profile), MSG_MEMBERSHIP_EXPIRED);
+        Ok((id, profile))
+    }
+
+    fn ensure_active_paid_terms(
+        paid_terms_id: u32
+    ) -> rstd::result::Result<PaidMembershipTerms<BalanceOf<T>, T::BlockNumber>, &'static str> {
+        Self::active_paid_terms()
+            .into_iter()
+            .find(|terms| terms.id == paid_terms_id)
+            .ok_or(MSG_PAID_TERMS_NOT_FOUND)
+    }
+
+    fn pay_fee(who: &T::AccountId, fee: BalanceOf<T>) -> Result {
+        ensure!(T::Currency::free_balance(who) >= fee, MSG_NOT_ENOUGH_BALANCE_TO_BUY_MEMBERSHIP);
+        if !fee.is_zero() {
+            let _ = T::Currency::slash(who, fee);
+        }
+        Ok(())
+    }
+
+    fn ensure_can_register(who: &T::AccountId, user_info: &UserInfo) -> Result {
+        ensure!(Self::new_memberships_allowed(), MSG_NEW_MEMBERS_NOT_ALLOWED);
+        ensure!(!<MemberIdByAccountId<T>>::exists(who), MSG_ALREADY_A_MEMBER);
+
+        Self::validate_handle(&user_info.handle)?;
+        Self::validate_avatar_uri(&user_info.avatar_uri)?;
+        Self::validate_about_text(&user_info.about)?;
+        Ok(())
+    }
+
+    fn validate_handle(handle: &Vec<u8>) -> Result {
+        ensure!(handle.len() as u32 >= Self::min_handle_length(), MSG_TOO_SHORT_HANDLE);
+        ensure!(handle.len() as u32 <= Self::max_handle_length(), MSG_TOO_LONG_HANDLE);
+
+        let allowed_chars = Self::handle_allowed_chars();
+        ensure!(handle.iter().all(|c| allowed_chars.contains(c)), MSG_HANDLE_HAS_INVALID_CHARS);
+
+        if let Some(holder) = Self::account_id_by_handle(handle) {
+            ensure!(!Self::is_active_member(&holder), MSG_HANDLE_ALREADY_TAKEN);
+        }
+        Ok(())
+    }
+
+    /// Point a handle to a given account. If the handle belonged to an expired member,
+    /// the member loses it and has to choose a new handle after renewing membership.
+    fn assign_handle(handle: &Vec<u8>, account: &T::AccountId) {
+        let previous_id = Self::account_id_by_handle(handle)
+            .and_then(|holder| Self::member_id_by_account_id(holder));
+        if let Some(id) = previous_id {
+            <MemberProfile<T>>::mutate(id, |profile| {
+                if let Some(profile) = profile {
+                    profile.handle = vec![];
+                }
+            });
+        }
+        <AccountIdByHandle<T>>::insert(handle, account.clone());
+    }
+
+    fn validate_avatar_uri(avatar_uri: &Vec<u8>) -> Result {
+        ensure!(avatar_uri.len() as u32 <= Self::max_avatar_uri_length(), MSG_TOO_LONG_AVATAR_URI);
+        Ok(())
+    }
+
+    fn validate_about_text(about: &Vec<u8>) -> Result {
+        ensure!(about.len() as u32 <= Self::max_about_text_length(), MSG_TOO_LONG_ABOUT_TEXT);
+        Ok(())
+    }
+
+    fn insert_member(
+        account: T::AccountId,
+        user_info: UserInfo,
+        entry: EntryMethod<T::AccountId>,
+        expires_at: T::BlockNumber,
+        invites: u32
+    ) -> MemberId {
+        let id = Self::member_count() + 1;
+        <MemberCount<T>>::put(id);
+
+        let profile = Profile {
+            id,
+            account: account.clone(),
+            handle: user_info.handle,
+            avatar_uri: user_info.avatar_uri,
+            about: user_info.about,
+            registered_at: <system::Module<T>>::block_number(),
+            expires_at,
+            entry,
+            invites,
+        };
+        Self::assign_handle(&profile.handle, &account);
+        <MemberIdByAccountId<T>>::insert(account, id);
+        <MemberProfile<T>>::insert(id, profile);
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::membership::mock::*;
+    use runtime_io::with_externalities;
+    use srml_support::*;
+
+    const ALICE: u64 = 1;
+    const BOB: u64 = 2;
+    const CHARLIE: u64 = 3;
+
+    fn user_info(handle: &[u8]) -> UserInfo {
+        UserInfo {
+            handle: handle.to_vec(),
+            avatar_uri: b"https://example.com/avatar.png".to_vec(),
+            about: b"About me".to_vec(),
+        }
+    }
+
+    fn handle_update(handle: &[u8]) -> UserInfoUpdate {
+        UserInfoUpdate {
+            handle: Some(handle.to_vec()),
+            ..Default::default()
+        }
+    }
+
+    fn buy_default_membership(account: u64, handle: &[u8]) -> Result {
+        Membership::buy_membership(Origin::signed(account), DEFAULT_PAID_TERMS_ID, user_info(handle))
+    }
+
+    fn short_terms() -> PaidMembershipTerms<u32, u64> {
+        PaidMembershipTerms { id: 2, fee: 10, duration: 10 }
+    }
+
+    #[test]
+    fn genesis_members_are_active() {
+        let mut config = members::GenesisConfig::<Test>::default();
+        config.members = vec![(ALICE, user_info(b"alice"), 100), (BOB, user_info(b"bobby"), 100)];
+        config.invites_per_member = 2;
+        let mut t = system::GenesisConfig::<Test>::default().build_storage().unwrap().0;
+        t.extend(config.build_storage().unwrap().0);
+
+        with_externalities(&mut runtime_io::TestExternalities::new(t), || {
+            assert!(Membership::is_active_member(&ALICE));
+            assert!(Membership::is_active_member(&BOB));
+            assert_eq!(Membership::member_count(), 2);
+            assert_eq!(Membership::member_id_by_account_id(BOB), Some(2));
+            assert_eq!(Membership::account_id_by_handle(b"alice".to_vec()), Some(ALICE));
+
+            let profile = Membership::member_profile(1).unwrap();
+            assert_eq!(profile.entry, EntryMethod::Genesis);
+            assert_eq!(profile.expires_at, 100);
+            assert_eq!(profile.invites, 2);
+
+            // Ids of new members continue after genesis members:
+            Balances::set_free_balance(&CHARLIE, 1000);
+            assert_ok!(buy_default_membership(CHARLIE, b"charlie"));
+            assert_eq!(Membership::member_id_by_account_id(CHARLIE), Some(3));
+        });
+    }
+
+    #[test]
+    fn buy_membership() {
+        with_externalities(&mut initial_test_ext(), || {
+            System::set_block_number(1);
+            Balances::set_free_balance(&ALICE, 150);
+
+            assert_ok!(buy_default_membership(ALICE, b"alice"));
+
+            assert!(Membership::is_active_member(&ALICE));
+            assert_eq!(Membership::member_id_by_account_id(ALICE), Some(1));
+            assert_eq!(Membership::account_id_by_handle(b"alice".to_vec()), Some(ALICE));
+
+            let profile = Membership::member_profile(1).unwrap();
+            assert_eq!(profile.handle, b"alice".to_vec());
+            assert_eq!(profile.entry, EntryMethod::Paid(DEFAULT_PAID_TERMS_ID));
+            assert_eq!(profile.expires_at, 1 + DEFAULT_PAID_TERMS_DURATION);
+            assert_eq!(profile.invites, DEFAULT_INVITES_PER_MEMBER);
+            assert_eq!(Balances::free_balance(&ALICE), 50);
+
+            assert_eq!(buy_default_membership(ALICE, b"alice2"), Err(MSG_ALREADY_A_MEMBER));
+        });
+    }
+
+    #[test]
+    fn cannot_buy_membership_without_paying_fee() {
+        with_externalities(&mut initial_test_ext(), || {
+            Balances::set_free_balance(&ALICE, 99);
+
+            assert_eq!(buy_default_membership(ALICE, b"alice"),
+                Err(MSG_NOT_ENOUGH_BALANCE_TO_BUY_MEMBERSHIP));
+            assert_eq!(Membership::buy_membership(Origin::signed(ALICE), 2, user_info(b"alice")),
+                Err(MSG_PAID_TERMS_NOT_FOUND));
+            assert!(!Membership::is_active_member(&ALICE));
+        });
+    }
+
+    #[test]
+    fn membership_expires_and_can_be_renewed() {
+        with_externalities(&mut initial_test_ext(), || {
+            System::set_block_number(1);
+            Balances::set_free_balance(&ALICE, 1000);
+            assert_ok!(Membership::add_paid_terms(Origin::ROOT, short_terms()));
+
+            assert_ok!(Membership::buy_membership(Origin::signed(ALICE), 2, user_info(b"alice")));
+            assert!(Membership::is_active_member(&ALICE));
+
+            System::set_block_number(11);
+            assert!(!Membership::is_active_member(&ALICE));
+            assert_eq!(Membership::update_profile(Origin::signed(ALICE), handle_update(b"alice2")),
+                Err(MSG_MEMBERSHIP_EXPIRED));
+
+            assert_ok!(Membership::renew_membership(Origin::signed(ALICE), 2));
+            assert!(Membership::is_active_member(&ALICE));
+            assert_eq!(Membership::member_profile(1).unwrap().expires_at, 21);
+
+            // Renewal of an active membership extends it from its current expiry:
+            assert_ok!(Membership::renew_membership(Origin::signed(ALICE), 2));
+            assert_eq!(Membership::member_profile(1).unwrap().expires_at, 31);
+            assert_eq!(Balances::free_balance(&ALICE), 970);
+        });
+    }
+
+    #[test]
+    fn council_can_manage_paid_terms() {
+        with_externalities(&mut initial_test_ext(), || {
+            assert_ok!(Membership::add_paid_terms(Origin::ROOT, short_terms()));
+            assert_eq!(Membership::add_paid_terms(Origin::ROOT, short_terms()), Err(MSG_PAID_TERMS_ALREADY_EXIST));
+            assert_eq!(Membership::add_paid_terms(Origin::ROOT, PaidMembershipTerms { id: 3, fee: 10, duration: 0 }),
+                Err(MSG_ZERO_PAID_TERMS_DURATION));
+            assert_eq!(Membership::active_paid_terms().len(), 2);
+
+            assert_ok!(Membership::remove_paid_terms(Origin::ROOT, DEFAULT_PAID_TERMS_ID));
+            assert_eq!(Membership::active_paid_terms(), vec![short_terms()]);
+            assert_eq!(Membership::remove_paid_terms(Origin::ROOT, DEFAULT_PAID_TERMS_ID), Err(MSG_PAID_TERMS_NOT_FOUND));
+        });
+    }
+
+    #[test]
+    fn only_council_can_change_parameters() {
+        with_externalities(&mut initial_test_ext(), || {
+            assert!(Membership::add_paid_terms(Origin::signed(ALICE), short_terms()).is_err());
+            assert!(Membership::remove_paid_terms(Origin::signed(ALICE), DEFAULT_PAID_TERMS_ID).is_err());
+            assert!(Membership::set_new_memberships_allowed(Origin::signed(ALICE), false).is_err());
+            assert!(Membership::set_invites_per_member(Origin::signed(ALICE), 0).is_err());
+            assert!(Membership::add_invites(Origin::signed(ALICE), ALICE, 1).is_err());
+            assert!(Membership::set_handle_rules(Origin::signed(ALICE), 1, 2, b"a".to_vec()).is_err());
+            assert!(Membership::new_memberships_allowed());
+        });
+    }
+
+    #[test]
+    fn handle_of_expired_member_can_be_taken() {
+        with_externalities(&mut initial_test_ext(), || {
+            System::set_block_number(1);
+            Balances::set_free_balance(&ALICE, 1000);
+            Balances::set_free_balance(&BOB, 1000);
+            assert_ok!(Membership::add_paid_terms(Origin::ROOT, short_terms()));
+            assert_ok!(Membership::buy_membership(Origin::signed(ALICE), 2, user_info(b"alice")));
+            assert_eq!(buy_default_membership(BOB, b"alice"), Err(MSG_HANDLE_ALREADY_TAKEN));
+
+            System::set_block_number(11);
+            assert_ok!(buy_default_membership(BOB, b"alice"));
+            assert_eq!(Membership::account_id_by_handle(b"alice".to_vec()), Some(BOB));
+            assert!(Membership::member_profile(1).unwrap().handle.is_empty());
+
+            // The former holder renews membership and chooses a new handle:
+            assert_ok!(Membership::renew_membership(Origin::signed(ALICE), 2));
+            assert_eq!(Membership::update_profile(Origin::signed(ALICE), handle_update(b"alice")),
+                Err(MSG_HANDLE_ALREADY_TAKEN));
+            assert_ok!(Membership::update_profile(Origin::signed(ALICE), handle_update(b"alice2")));
+            assert_eq!(Membership::account_id_by_handle(b"alice2".to_vec()), Some(ALICE));
+        });
+    }
+
+    #[test]
+    fn handle_should_follow_rules() {
+        with_externalities(&mut initial_test_ext(), || {
+            Balances::set_free_balance(&ALICE, 1000);
+            Balances::set_free_balance(&BOB, 1000);
+
+            assert_eq!(buy_default_membership(ALICE, b"al"), Err(MSG_TOO_SHORT_HANDLE));
+
+            let long_handle = vec![b'a'; DEFAULT_MAX_HANDLE_LENGTH as usize + 1];
+            assert_eq!(buy_default_membership(ALICE, &long_handle), Err(MSG_TOO_LONG_HANDLE));
+
+            assert_eq!(buy_default_membership(ALICE, b"Alice"), Err(MSG_HANDLE_HAS_INVALID_CHARS));
+            assert_eq!(buy_default_membership(ALICE, b"alice smith"), Err(MSG_HANDLE_HAS_INVALID_CHARS));
+
+            assert_ok!(buy_default_membership(ALICE, b"alice"));
+            assert_eq!(buy_default_membership(BOB, b"alice"), Err(MSG_HANDLE_ALREADY_TAKEN));
+
+            assert_ok!(Membership::set_handle_rules(Origin::ROOT, 2, 10, b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ".to_vec()));
+            assert_ok!(buy_default_membership(BOB, b"Bo"));
+        });
+    }
+
+    #[test]
+    fn cannot_register_with_invalid_user_info() {
+        with_externalities(&mut initial_test_ext(), || {
+            Balances::set_free_balance(&ALICE, 1000);
+
+            let mut info = user_info(b"alice");
+            info.about = vec![b'a'; DEFAULT_MAX_ABOUT_TEXT_LENGTH as usize + 1];
+            assert_eq!(Membership::buy_membership(Origin::signed(ALICE), DEFAULT_PAID_TERMS_ID, info),
+                Err(MSG_TOO_LONG_ABOUT_TEXT));
+
+            let mut info = user_info(b"alice");
+            info.avatar_uri = vec![b'a'; DEFAULT_MAX_AVATAR_URI_LENGTH as usize + 1];
+            assert_eq!(Membership::buy_membership(Origin::signed(ALICE), DEFAULT_PAID_TERMS_ID, info),
+                Err(MSG_TOO_LONG_AVATAR_URI));
+        });
+    }
+
+    #[test]
+    fn member_can_update_profile() {
+        with_externalities(&mut initial_test_ext(), || {
+            Balances::set_free_balance(&ALICE, 1000);
+            Balances::set_free_balance(&BOB, 1000);
+            assert_ok!(buy_default_membership(ALICE, b"alice"));
+            assert_ok!(buy_default_membership(BOB, b"bobby"));
+
+            assert_eq!(Membership::update_profile(Origin::signed(CHARLIE), handle_update(b"charlie")),
+                Err(MSG_NOT_A_MEMBER));
+            assert_eq!(Membership::update_profile(Origin::signed(ALICE), handle_update(b"bobby")),
+                Err(MSG_HANDLE_ALREADY_TAKEN));
+
+            // Keeping the same handle is not an error:
+            assert_ok!(Membership::update_profile(Origin::signed(ALICE), UserInfoUpdate {
+                handle: Some(b"alice".to_vec()),
+                about: Some(b"New about".to_vec()),
+                ..Default::default()
+            }));
+
+            assert_ok!(Membership::update_profile(Origin::signed(ALICE), handle_update(b"alice_smith")));
+            let profile = Membership::member_profile(1).unwrap();
+            assert_eq!(profile.handle, b"alice_smith".to_vec());
+            assert_eq!(profile.about, b"New about".to_vec());
+            assert_eq!(Membership::account_id_by_handle(b"alice_smith".to_vec()), Some(ALICE));
+            assert_eq!(Membership::account_id_by_handle(b"alice".to_vec()), None);
+
+            // A released handle can be taken by another member:
+            assert_ok!(Membership::update_profile(Origin::signed(BOB), handle_update(b"alice")));
+            assert_eq!(Membership::account_id_by_handle(b"alice".to_vec()), Some(BOB));
+        });
+    }
+
+    #[test]
+    fn member_can_invite_until_invites_run_out() {
+        with_externalities(&mut initial_test_ext(), || {
+            Balances::set_free_balance(&ALICE, 1000);
+            assert_ok!(Membership::set_invites_per_member(Origin::ROOT, 1));
+            assert_ok!(buy_default_membership(ALICE, b"alice"));
+
+            assert_eq!(Membership::invite_member(Origin::signed(BOB), CHARLIE, user_info(b"charlie")),
+                Err(MSG_NOT_A_MEMBER));
+
+            assert_ok!(Membership::invite_member(Origin::signed(ALICE), BOB, user_info(b"bobby")));
+            assert!(Membership::is_active_member(&BOB));
+            let bob = Membership::member_profile(2).unwrap();
+            assert_eq!(bob.entry, EntryMethod::Invited(ALICE));
+            assert_eq!(bob.expires_at, Membership::member_profile(1).unwrap().expires_at);
+            assert_eq!(Membership::member_profile(1).unwrap().invites, 0);
+
+            assert_eq!(Membership::invite_member(Origin::signed(ALICE), CHARLIE, user_info(b"charlie")),
+                Err(MSG_NO_INVITES_LEFT));
+
+            assert_ok!(Membership::add_invites(Origin::ROOT, ALICE, 1));
+            assert_ok!(Membership::invite_member(Origin::signed(ALICE), CHARLIE, user_info(b"charlie")));
+            assert!(Membership::is_active_member(&CHARLIE));
+        });
+    }
+
+    #[test]
+    fn new_memberships_can_be_disallowed() {
+        with_externalities(&mut initial_test_ext(), || {
+            Balances::set_free_balance(&ALICE, 1000);
+            assert_ok!(Membership::set_new_memberships_allowed(Origin::ROOT, false));
+
+            assert_eq!(buy_default_membership(ALICE, b"alice"), Err(MSG_NEW_MEMBERS_NOT_ALLOWED));
+        });
+    }
+}
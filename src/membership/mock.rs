@@ -0,0 +1,74 @@
+#![cfg(test)]
+
+pub use super::members;
+pub use crate::governance::GovernanceCurrency;
+pub use crate::governance::council::EnsureCouncilOrigin;
+pub use system;
+
+pub use primitives::{H256, Blake2Hasher};
+pub use runtime_primitives::{
+    BuildStorage,
+    traits::{BlakeTwo256, IdentityLookup},
+    testing::{Digest, DigestItem, Header}
+};
+
+use srml_support::{impl_outer_origin, dispatch::Result};
+
+impl_outer_origin! {
+    pub enum Origin for Test {}
+}
+
+// For testing the module, we construct most of a mock runtime. This means
+// first constructing a configuration type (`Test`) which `impl`s each of the
+// configuration traits of modules we want to use.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+impl system::Trait for Test {
+    type Origin = Origin;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type Digest = Digest;
+    type AccountId = u64;
+    type Header = Header;
+    type Event = ();
+    type Log = DigestItem;
+    type Lookup = IdentityLookup<u64>;
+}
+impl balances::Trait for Test {
+    type Event = ();
+    type Balance = u32;
+    type OnFreeBalanceZero = ();
+    type OnNewAccount = ();
+    type EnsureAccountLiquid = ();
+}
+
+impl GovernanceCurrency for Test {
+    type Currency = balances::Module<Self>;
+}
+
+/// The mock runtime has no council, so only root can change membership parameters.
+pub struct RootOnly;
+impl EnsureCouncilOrigin<Origin> for RootOnly {
+    fn ensure_council_origin(o: Origin) -> Result {
+        system::ensure_root(o)
+    }
+}
+
+impl members::Trait for Test {
+    type Event = ();
+    type CouncilOrigin = RootOnly;
+}
+
+// This function basically just builds a genesis storage key/value store according to
+// our desired mockup.
+pub fn initial_test_ext() -> runtime_io::TestExternalities<Blake2Hasher> {
+    let t = system::GenesisConfig::<Test>::default().build_storage().unwrap().0;
+
+    runtime_io::TestExternalities::new(t)
+}
+
+pub type Membership = members::Module<Test>;
+pub type System = system::Module<Test>;
+pub type Balances = balances::Module<Test>;
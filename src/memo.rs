@@ -1,14 +1,16 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use srml_support::{StorageMap, dispatch::Result, decl_module, decl_storage, decl_event, ensure};
-use srml_support::traits::{Currency};
-use runtime_primitives::traits::{Zero};
+use srml_support::{StorageMap, StorageValue, dispatch::Result, decl_module, decl_storage, decl_event, ensure};
 use system::{self, ensure_signed};
 use rstd::prelude::*;
 use crate::governance::GovernanceCurrency;
+use crate::governance::council::{self, EnsureCouncilOrigin};
+use crate::traits::Members;
 
-pub trait Trait: system::Trait + GovernanceCurrency {
+pub trait Trait: system::Trait + GovernanceCurrency + council::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+    type Members: Members<Self>;
 }
 
 decl_storage! {
@@ -21,6 +23,7 @@ decl_storage! {
 decl_event! {
     pub enum Event<T> where <T as system::Trait>::AccountId {
         MemoUpdated(AccountId),
+        MaxMemoLengthUpdated(u32),
     }
 }
 
@@ -31,11 +34,20 @@ decl_module! {
         fn update_memo(origin, memo: Vec<u8>) {
             let sender = ensure_signed(origin)?;
 
-            ensure!(!T::Currency::total_balance(&sender).is_zero(), "account must have a balance");
+            ensure!(T::Members::is_active_member(&sender), "only members can update memo");
             ensure!(memo.len() as u32 <= Self::max_memo_length(), "memo too long");
 
             <Memo<T>>::insert(sender.clone(), memo);
             Self::deposit_event(RawEvent::MemoUpdated(sender));
         }
+
+        /// Can be called by sudo or a council majority, e.g. through a council motion.
+        fn set_max_memo_length(origin, max_memo_length: u32) {
+            T::CouncilOrigin::ensure_council_origin(origin)?;
+            ensure!(max_memo_length > 0, "max memo length must be greater than zero");
+
+            <MaxMemoLength<T>>::put(max_memo_length);
+            Self::deposit_event(RawEvent::MaxMemoLengthUpdated(max_memo_length));
+        }
     }
 }
@@ -0,0 +1,15 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use system;
+
+// Members
+
+pub trait Members<T: system::Trait> {
+    fn is_active_member(account: &T::AccountId) -> bool;
+}
+
+impl<T: system::Trait> Members<T> for () {
+    fn is_active_member(_account: &T::AccountId) -> bool {
+        false
+    }
+}